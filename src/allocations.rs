@@ -0,0 +1,224 @@
+//! Counting how much the solutions allocate.
+//!
+//! With the `count-allocations` feature, [`Counting`] becomes the global
+//! allocator. It keeps counts for each thread, so parts solved at the same
+//! time on different threads are measured separately. Without the feature
+//! [`measure`] doesn't measure anything, and allocating costs nothing extra.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+
+/// What a closure run through [`measure`] allocated
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Allocations {
+    /// Allocations and reallocations
+    pub count: u64,
+    /// Bytes allocated, counting a reallocation as the bytes it grew by
+    pub bytes: u64,
+    /// The most bytes that were allocated and not yet freed at any one
+    /// time, not counting what was already allocated beforehand
+    pub peak_bytes: u64,
+}
+
+impl fmt::Display for Allocations {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}, {} allocated, {} peak",
+            self.count,
+            if self.count == 1 {
+                "allocation"
+            } else {
+                "allocations"
+            },
+            Bytes(self.bytes),
+            Bytes(self.peak_bytes),
+        )
+    }
+}
+
+/// A number of bytes in B, KiB, MiB or GiB
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut size = self.0 as f64;
+        for unit in ["B", "KiB", "MiB"] {
+            if size < 1024.0 {
+                return if unit == "B" {
+                    write!(f, "{} B", self.0)
+                } else {
+                    write!(f, "{size:.1} {unit}")
+                };
+            }
+            size /= 1024.0;
+        }
+        write!(f, "{size:.1} GiB")
+    }
+}
+
+/// Whether [`Counting`] is the global allocator
+pub const ENABLED: bool = cfg!(feature = "count-allocations");
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// The system allocator, keeping count of what each thread allocates
+pub struct Counting;
+
+#[derive(Clone, Copy)]
+struct Counts {
+    count: u64,
+    bytes: u64,
+    /// Can go below zero when memory allocated on another thread is freed
+    /// on this one
+    live: i64,
+    peak: i64,
+}
+
+thread_local! {
+    // Without a destructor, so it can be used while the thread exits
+    static COUNTS: Cell<Counts> = const {
+        Cell::new(Counts {
+            count: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+fn record(allocated: bool, change: i64) {
+    COUNTS.with(|counts| {
+        let mut new = counts.get();
+        if allocated {
+            new.count += 1;
+        }
+        if change > 0 {
+            new.bytes += change as u64;
+        }
+        new.live += change;
+        new.peak = new.peak.max(new.live);
+        counts.set(new);
+    });
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(true, layout.size() as i64);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(true, layout.size() as i64);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(false, -(layout.size() as i64));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(true, new_size as i64 - layout.size() as i64);
+        }
+        new_ptr
+    }
+}
+
+/// Runs `f`, counting what it allocates on this thread. Returns `None`
+/// alongside its result if [`Counting`] isn't the global allocator.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+    if !ENABLED {
+        return (f(), None);
+    }
+    let before = COUNTS.with(|counts| {
+        let mut before = counts.get();
+        before.peak = before.live;
+        counts.set(before);
+        before
+    });
+    let result = f();
+    let after = COUNTS.with(Cell::get);
+    let allocations = Allocations {
+        count: after.count - before.count,
+        bytes: after.bytes - before.bytes,
+        peak_bytes: (after.peak - before.live).max(0) as u64,
+    };
+    (result, Some(allocations))
+}
+
+/// Runs `f` without counting what it allocates towards a [`measure`]
+/// around it, for bookkeeping that isn't part of what's being measured
+pub fn ignoring<T>(f: impl FnOnce() -> T) -> T {
+    if !ENABLED {
+        return f();
+    }
+    let before = COUNTS.with(Cell::get);
+    let result = f();
+    COUNTS.with(|counts| counts.set(before));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measuring() {
+        let (sum, allocations) = measure(|| {
+            let mut numbers: Vec<u64> = Vec::with_capacity(100);
+            numbers.extend(0..100);
+            let sum: u64 = numbers.iter().sum();
+            drop(numbers);
+            let _small = Box::new(sum);
+            sum
+        });
+        assert_eq!(sum, 4950);
+        if !ENABLED {
+            assert_eq!(allocations, None);
+            return;
+        }
+        assert_eq!(
+            allocations,
+            Some(Allocations {
+                count: 2,
+                bytes: 808,
+                peak_bytes: 800,
+            })
+        );
+    }
+
+    #[test]
+    fn displaying() {
+        let allocations = Allocations {
+            count: 1,
+            bytes: 3 << 20,
+            peak_bytes: 1000,
+        };
+        assert_eq!(
+            allocations.to_string(),
+            "1 allocation, 3.0 MiB allocated, 1000 B peak"
+        );
+        assert_eq!(Bytes(1536).to_string(), "1.5 KiB");
+        assert_eq!(Bytes(5 << 30).to_string(), "5.0 GiB");
+    }
+
+    #[test]
+    fn ignoring_bookkeeping() {
+        let (_, allocations) = measure(|| ignoring(|| vec![0u8; 100]));
+        if ENABLED {
+            assert_eq!(allocations, Some(Allocations::default()));
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use crate::parse::ParseError;
+use crate::toml;
+use crate::{Answer, Context, Day, Parsed, Part};
+
+/// The expected answers for the bundled inputs
+pub const ANSWERS: &str = include_str!("../answers.toml");
+
+/// Expected answers for each day.
+///
+/// These are read from a small subset of TOML: a `[dayN]` table per day with
+/// `part1` and `part2` keys holding an integer or a (multi-line) string.
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: BTreeMap<(u8, Part), String>,
+}
+
+/// The result of checking one part against its expected answer
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong {
+        expected: String,
+        actual: Answer,
+    },
+    /// There's no stored answer to check against. `actual` is `None` if the
+    /// part hasn't been solved either.
+    Missing {
+        actual: Option<Answer>,
+    },
+    /// The solution says the input has no answer
+    Failed {
+        error: String,
+    },
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut answers = BTreeMap::new();
+        for table in toml::parse(text, "a `[dayN]` table")? {
+            let day = toml::day_number(&table)?;
+            for entry in table.entries {
+                let part = match entry.key {
+                    "part1" => Part::One,
+                    "part2" => Part::Two,
+                    _ => return Err(entry.line.error("`part1` or `part2`")),
+                };
+                answers.insert((day, part), entry.value.into_string());
+            }
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    /// Solves one part of an already parsed day and checks its answer
+    pub fn verify(&self, day: &Day, parsed: &Parsed, part: Part) -> Verdict {
+        let actual = match day.solve(part, parsed, &Context::with_defaults(day)) {
+            Some(Err(error)) => return Verdict::Failed { error },
+            actual => actual.map(Result::unwrap),
+        };
+        match (self.get(day.number, part), actual) {
+            (Some(expected), Some(actual)) if expected == actual.to_string() => Verdict::Correct,
+            (Some(expected), Some(actual)) => Verdict::Wrong {
+                expected: expected.to_string(),
+                actual,
+            },
+            // A stored answer without a solution shouldn't happen, but
+            // there's nothing to check it against
+            (_, actual) => Verdict::Missing { actual },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_day;
+
+    #[test]
+    fn parsing_answers() {
+        let answers = Answers::parse(
+            r#"
+# A comment
+[day1]
+part1 = 24000
+part2 = "45000" # trailing comment
+
+[day10]
+part2 = """
+##..
+.##."""
+[day5]
+part1 = "C\"M\\Z"
+"#,
+        )
+        .unwrap();
+        assert_eq!(answers.get(1, Part::One), Some("24000"));
+        assert_eq!(answers.get(1, Part::Two), Some("45000"));
+        assert_eq!(answers.get(10, Part::One), None);
+        assert_eq!(answers.get(10, Part::Two), Some("##..\n.##."));
+        assert_eq!(answers.get(5, Part::One), Some("C\"M\\Z"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Answers::parse("part1 = 5").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: expected a `[dayN]` table, found `part1`"
+        );
+
+        let error = Answers::parse("[day1]\npart1 = \"\"\"\nabc").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 1: expected the closing `\"\"\"`, found end of line"
+        );
+    }
+
+    #[test]
+    fn checked_in_answers_parse() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.get(14, Part::Two), None);
+        assert_eq!(answers.get(10, Part::Two).unwrap().lines().count(), 6);
+    }
+
+    #[test]
+    fn verdicts() {
+        let answers = Answers::parse("[day6]\npart1 = 7\npart2 = 20").unwrap();
+        let day = get_day(6).unwrap();
+        let parsed = day.parse("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap();
+        assert_eq!(answers.verify(day, &parsed, Part::One), Verdict::Correct);
+        assert_eq!(
+            answers.verify(day, &parsed, Part::Two),
+            Verdict::Wrong {
+                expected: "20".to_string(),
+                actual: Answer::Number(19)
+            }
+        );
+
+        let day = get_day(14).unwrap();
+        let parsed = day.parse("498,4 -> 498,6 -> 496,6").unwrap();
+        assert_eq!(
+            answers.verify(day, &parsed, Part::Two),
+            Verdict::Missing { actual: None }
+        );
+    }
+}
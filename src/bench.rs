@@ -0,0 +1,365 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::json::Json;
+use crate::parse::ParseError;
+use crate::{Context, Day, Part};
+
+/// What one benchmark measures: parsing a day's input or solving one part
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl Stage {
+    fn name(self) -> &'static str {
+        match self {
+            Stage::Parse => "parse",
+            Stage::Part(Part::One) => "part1",
+            Stage::Part(Part::Two) => "part2",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "parse" => Some(Stage::Parse),
+            "part1" => Some(Stage::Part(Part::One)),
+            "part2" => Some(Stage::Part(Part::Two)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Padded, so stages line up in tables
+        match self {
+            Stage::Parse => f.pad("Parse"),
+            Stage::Part(part) => f.pad(&format!("Part {part}")),
+        }
+    }
+}
+
+/// How long to keep repeating each benchmark
+#[derive(Clone, Copy, Debug)]
+pub struct Settings {
+    /// Every benchmark runs at least this often, however slow it is
+    pub min_runs: usize,
+    pub max_runs: usize,
+    /// Runs are repeated until this much time has been spent, within the
+    /// limits above
+    pub target_time: Duration,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            min_runs: 3,
+            max_runs: 10_000,
+            target_time: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Summary of the timings of repeated runs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Returns `None` if there are no timings
+    pub fn from_timings(mut timings: Vec<Duration>) -> Option<Self> {
+        timings.sort_unstable();
+        // Nearest-rank percentiles, so every statistic is a real run
+        let percentile = |p: usize| timings[(timings.len() * p).div_ceil(100).max(1) - 1];
+        Some(Stats {
+            runs: timings.len(),
+            min: *timings.first()?,
+            median: percentile(50),
+            p95: percentile(95),
+        })
+    }
+}
+
+/// Runs `f` repeatedly, after one untimed warm-up run
+pub fn measure<T>(settings: &Settings, mut f: impl FnMut() -> T) -> Stats {
+    std::hint::black_box(f());
+
+    let start = Instant::now();
+    let mut timings = Vec::new();
+    while timings.len() < settings.min_runs.max(1)
+        || (timings.len() < settings.max_runs && start.elapsed() < settings.target_time)
+    {
+        let run = Instant::now();
+        std::hint::black_box(f());
+        timings.push(run.elapsed());
+    }
+    Stats::from_timings(timings).expect("benchmark ran at least once")
+}
+
+/// The timings of one stage of one day
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Measurement {
+    pub day: u8,
+    pub stage: Stage,
+    pub stats: Stats,
+}
+
+/// Benchmarks parsing `day`'s input and then each of the requested parts it
+/// has a solution for, which is found by solving each part once first
+pub fn bench_day(
+    day: &Day,
+    input: &str,
+    parts: &[Part],
+    settings: &Settings,
+) -> Result<Vec<Measurement>, ParseError> {
+    let parsed = day.parse(input)?;
+    // Always the default parameters, so reports stay comparable
+    let context = Context::with_defaults(day);
+    let mut measurements = vec![Measurement {
+        day: day.number,
+        stage: Stage::Parse,
+        stats: measure(settings, || day.parse(input)),
+    }];
+    for &part in parts {
+        if day.solve(part, &parsed, &context).is_none() {
+            continue;
+        }
+        measurements.push(Measurement {
+            day: day.number,
+            stage: Stage::Part(part),
+            stats: measure(settings, || day.solve(part, &parsed, &context)),
+        });
+    }
+    Ok(measurements)
+}
+
+/// A set of measurements that can be saved as JSON and used as a baseline
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    pub measurements: Vec<Measurement>,
+}
+
+impl Report {
+    pub fn to_json(&self) -> Json {
+        let nanos = |duration: Duration| u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        let results = self
+            .measurements
+            .iter()
+            .map(|measurement| {
+                Json::object()
+                    .with("day", measurement.day)
+                    .with("stage", measurement.stage.name())
+                    .with("runs", measurement.stats.runs)
+                    .with("min_ns", nanos(measurement.stats.min))
+                    .with("median_ns", nanos(measurement.stats.median))
+                    .with("p95_ns", nanos(measurement.stats.p95))
+            })
+            .collect::<Vec<_>>();
+        Json::object().with("results", results)
+    }
+
+    pub fn from_json(json: &Json) -> Result<Self, String> {
+        let results = json
+            .get("results")
+            .and_then(Json::as_array)
+            .ok_or("expected an object with a `results` array")?;
+        let measurements = results
+            .iter()
+            .enumerate()
+            .map(|(index, result)| {
+                let field = |key: &str| {
+                    result
+                        .get(key)
+                        .ok_or_else(|| format!("result {} is missing `{key}`", index + 1))
+                };
+                let integer = |key: &str| {
+                    field(key)?.as_i64().ok_or_else(|| {
+                        format!("`{key}` of result {} should be an integer", index + 1)
+                    })
+                };
+                let duration = |key: &str| {
+                    let nanos = u64::try_from(integer(key)?).map_err(|_| {
+                        format!("`{key}` of result {} can't be negative", index + 1)
+                    })?;
+                    Ok::<_, String>(Duration::from_nanos(nanos))
+                };
+
+                let stage = field("stage")?
+                    .as_str()
+                    .and_then(Stage::from_name)
+                    .ok_or_else(|| {
+                        format!(
+                            "`stage` of result {} should be \"parse\", \"part1\" or \"part2\"",
+                            index + 1
+                        )
+                    })?;
+                Ok(Measurement {
+                    day: u8::try_from(integer("day")?)
+                        .map_err(|_| format!("`day` of result {} is out of range", index + 1))?,
+                    stage,
+                    stats: Stats {
+                        runs: usize::try_from(integer("runs")?).map_err(|_| {
+                            format!("`runs` of result {} can't be negative", index + 1)
+                        })?,
+                        min: duration("min_ns")?,
+                        median: duration("median_ns")?,
+                        p95: duration("p95_ns")?,
+                    },
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Report { measurements })
+    }
+
+    pub fn get(&self, day: u8, stage: Stage) -> Option<&Measurement> {
+        self.measurements
+            .iter()
+            .find(|measurement| measurement.day == day && measurement.stage == stage)
+    }
+}
+
+/// A stage whose median got slower than its baseline by more than the
+/// allowed threshold
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Regression {
+    pub day: u8,
+    pub stage: Stage,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Regression {
+    /// How much slower the current median is, e.g. 0.25 for 25% slower
+    pub fn slowdown(&self) -> f64 {
+        self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0
+    }
+}
+
+/// Compares medians, since they're the least affected by the odd slow run.
+/// `threshold` is the allowed slowdown, e.g. 0.1 to allow 10%. Stages that
+/// aren't in the baseline are skipped.
+pub fn find_regressions(baseline: &Report, current: &Report, threshold: f64) -> Vec<Regression> {
+    current
+        .measurements
+        .iter()
+        .filter_map(|measurement| {
+            let baseline = baseline.get(measurement.day, measurement.stage)?;
+            let regression = Regression {
+                day: measurement.day,
+                stage: measurement.stage,
+                baseline: baseline.stats.median,
+                current: measurement.stats.median,
+            };
+            (regression.current > regression.baseline && regression.slowdown() > threshold)
+                .then_some(regression)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_day;
+
+    fn millis(millis: &[u64]) -> Vec<Duration> {
+        millis.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    fn measurement(day: u8, stage: Stage, median_ms: u64) -> Measurement {
+        let median = Duration::from_millis(median_ms);
+        Measurement {
+            day,
+            stage,
+            stats: Stats {
+                runs: 10,
+                min: median,
+                median,
+                p95: median,
+            },
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::from_timings(millis(&[5, 1, 4, 2, 3])).unwrap();
+        assert_eq!(stats.runs, 5);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+
+        let timings = millis(&(1..=100).collect::<Vec<_>>());
+        let stats = Stats::from_timings(timings).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(50));
+        assert_eq!(stats.p95, Duration::from_millis(95));
+
+        assert_eq!(Stats::from_timings(Vec::new()), None);
+    }
+
+    #[test]
+    fn measuring_a_day() {
+        let settings = Settings {
+            min_runs: 2,
+            max_runs: 2,
+            target_time: Duration::ZERO,
+        };
+        let day = get_day(14).unwrap();
+        let measurements =
+            bench_day(day, "498,4 -> 498,6 -> 496,6", &Part::ALL, &settings).unwrap();
+        let stages: Vec<_> = measurements.iter().map(|m| m.stage).collect();
+        assert_eq!(stages, [Stage::Parse, Stage::Part(Part::One)]);
+        assert!(measurements.iter().all(|m| m.stats.runs == 2));
+    }
+
+    #[test]
+    fn stages_in_columns() {
+        assert_eq!(format!("{:<7}|", Stage::Parse), "Parse  |");
+        assert_eq!(format!("{:<7}|", Stage::Part(Part::Two)), "Part 2 |");
+    }
+
+    #[test]
+    fn report_round_trip() {
+        let report = Report {
+            measurements: vec![
+                measurement(1, Stage::Parse, 1),
+                measurement(15, Stage::Part(Part::Two), 2_500),
+            ],
+        };
+        let json = Json::parse(&report.to_json().pretty()).unwrap();
+        assert_eq!(Report::from_json(&json).unwrap(), report);
+
+        let json = Json::parse(r#"{"results": [{"day": 1, "stage": "part3"}]}"#).unwrap();
+        assert_eq!(
+            Report::from_json(&json).unwrap_err(),
+            "`stage` of result 1 should be \"parse\", \"part1\" or \"part2\""
+        );
+    }
+
+    #[test]
+    fn regressions() {
+        let baseline = Report {
+            measurements: vec![
+                measurement(8, Stage::Part(Part::Two), 100),
+                measurement(11, Stage::Part(Part::Two), 100),
+                measurement(15, Stage::Part(Part::One), 100),
+            ],
+        };
+        let current = Report {
+            measurements: vec![
+                measurement(8, Stage::Part(Part::Two), 105),
+                measurement(11, Stage::Part(Part::Two), 150),
+                measurement(15, Stage::Part(Part::One), 50),
+                measurement(15, Stage::Part(Part::Two), 1_000),
+            ],
+        };
+        let regressions = find_regressions(&baseline, &current, 0.1);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].day, 11);
+        assert!((regressions[0].slowdown() - 0.5).abs() < 1e-9);
+    }
+}
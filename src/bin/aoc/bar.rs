@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::io::{self, Write};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use advent_of_code_2022::progress::{Listener, Progress};
+use advent_of_code_2022::Part;
+
+/// How often the bar is redrawn at most
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+const BAR_WIDTH: usize = 20;
+
+/// A progress bar on the last line of the terminal, with an entry for
+/// every part that's reported progress and is still running
+pub struct Bar {
+    state: Mutex<State>,
+    /// The terminal's width, so the line never wraps
+    columns: usize,
+}
+
+#[derive(Default)]
+struct State {
+    running: BTreeMap<(u8, Part), Progress>,
+    last_drawn: Option<Instant>,
+    /// Whether the bar is on screen
+    shown: bool,
+}
+
+impl Bar {
+    pub fn new() -> Self {
+        let columns = env::var("COLUMNS")
+            .ok()
+            .and_then(|columns| columns.parse().ok())
+            .unwrap_or(80);
+        Bar {
+            state: Mutex::default(),
+            columns,
+        }
+    }
+
+    /// Takes the bar off the screen while `f` prints something else. It
+    /// comes back with the next update.
+    pub fn suspend<T>(&self, f: impl FnOnce() -> T) -> T {
+        let mut state = self.state.lock().unwrap();
+        if state.shown {
+            print!("\r\x1b[2K");
+            let _ = io::stdout().flush();
+            state.shown = false;
+        }
+        f()
+    }
+
+    fn line(&self, running: &BTreeMap<(u8, Part), Progress>) -> String {
+        let line = running
+            .iter()
+            .map(|(&(day, part), progress)| {
+                let Progress { done, total, unit } = *progress;
+                match total {
+                    Some(total) if total > 0 => {
+                        let filled = (done * BAR_WIDTH / total).min(BAR_WIDTH);
+                        format!(
+                            "Day {day} part {part} [{}{}] {done}/{total} {unit}",
+                            "#".repeat(filled),
+                            " ".repeat(BAR_WIDTH - filled),
+                        )
+                    }
+                    _ => format!("Day {day} part {part}: {done} {unit}"),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        line.chars().take(self.columns.saturating_sub(1)).collect()
+    }
+}
+
+impl Listener for Bar {
+    fn update(&self, day: u8, part: Part, progress: Progress) {
+        let mut state = self.state.lock().unwrap();
+        state.running.insert((day, part), progress);
+        if state
+            .last_drawn
+            .is_some_and(|drawn| drawn.elapsed() < REDRAW_INTERVAL)
+        {
+            return;
+        }
+        print!("\r\x1b[2K{}", self.line(&state.running));
+        let _ = io::stdout().flush();
+        state.last_drawn = Some(Instant::now());
+        state.shown = true;
+    }
+
+    fn finished(&self, day: u8, part: Part) {
+        self.state.lock().unwrap().running.remove(&(day, part));
+    }
+}
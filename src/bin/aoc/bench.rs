@@ -0,0 +1,149 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use advent_of_code_2022::bench::{self, Report, Settings};
+use advent_of_code_2022::json::Json;
+use advent_of_code_2022::{Day, Part, DAYS};
+
+use crate::Args;
+
+pub struct Options {
+    days: Vec<&'static Day>,
+    parts: Vec<Part>,
+    settings: Settings,
+    save: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    /// The allowed slowdown before a stage counts as a regression
+    threshold: f64,
+}
+
+impl Options {
+    pub fn parse(args: &mut Args) -> Result<Self, String> {
+        let mut days = Vec::new();
+        let mut parts = Part::ALL.to_vec();
+        let mut settings = Settings::default();
+        let mut save = None;
+        let mut baseline = None;
+        let mut threshold = 0.1;
+        while let Some(arg) = args.next() {
+            match arg {
+                "--day" | "-d" => days.push(args.day(arg)?),
+                "--part" | "-p" => parts = vec![args.part(arg)?],
+                "--runs" => {
+                    let runs = args.value(arg)?;
+                    let runs: usize =
+                        runs.parse().ok().filter(|&runs| runs > 0).ok_or_else(|| {
+                            format!("--runs must be a positive number, not `{runs}`")
+                        })?;
+                    settings.min_runs = runs;
+                    settings.max_runs = runs;
+                }
+                "--time" => {
+                    let time = args.value(arg)?;
+                    settings.target_time = time
+                        .parse()
+                        .ok()
+                        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                        .ok_or_else(|| {
+                            format!("--time must be a number of seconds, not `{time}`")
+                        })?;
+                }
+                "--save" => save = Some(PathBuf::from(args.value(arg)?)),
+                "--baseline" => baseline = Some(PathBuf::from(args.value(arg)?)),
+                "--threshold" => {
+                    let percent = args.value(arg)?;
+                    threshold = percent
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|percent| *percent >= 0.0)
+                        .ok_or_else(|| {
+                            format!("--threshold must be a percentage, not `{percent}`")
+                        })?
+                        / 100.0;
+                }
+                other => return Err(format!("unexpected argument `{other}`")),
+            }
+        }
+        if days.is_empty() {
+            days = DAYS.iter().collect();
+        }
+        Ok(Options {
+            days,
+            parts,
+            settings,
+            save,
+            baseline,
+            threshold,
+        })
+    }
+}
+
+/// Benchmarks the bundled inputs. Returns `false` if anything got slower
+/// than the baseline.
+pub fn bench(options: Options) -> Result<bool, String> {
+    // Load the baseline first so a bad path fails before the slow part
+    let baseline = options
+        .baseline
+        .as_ref()
+        .map(|path| {
+            let text = fs::read_to_string(path)
+                .map_err(|err| format!("could not read `{}`: {err}", path.display()))?;
+            let json = Json::parse(&text).map_err(|err| format!("{}: {err}", path.display()))?;
+            Report::from_json(&json).map_err(|err| format!("{}: {err}", path.display()))
+        })
+        .transpose()?;
+
+    println!(
+        "{:<8} {:<7} {:>7} {:>11} {:>11} {:>11}",
+        "Day", "Stage", "Runs", "Min", "Median", "p95"
+    );
+    let mut report = Report::default();
+    for day in options.days {
+        let measurements = bench::bench_day(day, day.input, &options.parts, &options.settings)
+            .map_err(|err| err.to_string())?;
+        for measurement in measurements {
+            let stats = measurement.stats;
+            println!(
+                "{:<8} {:<7} {:>7} {:>11} {:>11} {:>11}",
+                format!("Day {}", measurement.day),
+                measurement.stage,
+                stats.runs,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.p95),
+            );
+            report.measurements.push(measurement);
+        }
+    }
+
+    if let Some(path) = &options.save {
+        fs::write(path, report.to_json().pretty() + "\n")
+            .map_err(|err| format!("could not write `{}`: {err}", path.display()))?;
+        println!("\nSaved results to {}", path.display());
+    }
+
+    let Some(baseline) = baseline else {
+        return Ok(true);
+    };
+    let regressions = bench::find_regressions(&baseline, &report, options.threshold);
+    if regressions.is_empty() {
+        println!(
+            "\nNo regressions against the baseline (threshold {}%)",
+            options.threshold * 100.0
+        );
+        return Ok(true);
+    }
+    println!("\nRegressions against the baseline:");
+    for regression in &regressions {
+        println!(
+            "  Day {} {}: median {:.2?} -> {:.2?} ({:+.1}%)",
+            regression.day,
+            regression.stage,
+            regression.baseline,
+            regression.current,
+            regression.slowdown() * 100.0
+        );
+    }
+    Ok(false)
+}
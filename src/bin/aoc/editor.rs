@@ -0,0 +1,174 @@
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+
+/// Reads lines typed at the terminal, finishing the last word with tab and
+/// going back through earlier lines with the arrow keys. When stdin isn't a
+/// terminal, like when queries are piped in, it just reads lines.
+pub struct Editor {
+    terminal: bool,
+    history: Vec<String>,
+}
+
+impl Editor {
+    pub fn new() -> Self {
+        Editor {
+            terminal: io::stdin().is_terminal() && io::stdout().is_terminal(),
+            history: Vec::new(),
+        }
+    }
+
+    /// Reads the next line, or returns `None` at the end of the input.
+    /// `complete` returns the words the last word of a line can be finished
+    /// to.
+    pub fn read_line(
+        &mut self,
+        prompt: &str,
+        complete: impl Fn(&str) -> Vec<String>,
+    ) -> io::Result<Option<String>> {
+        // Keys are read one at a time, without the terminal echoing them or
+        // turning Ctrl-C into a signal that would leave it in that state
+        let saved = match self.terminal {
+            true => stty(&["-g"]).ok(),
+            false => None,
+        };
+        let Some(saved) = saved.filter(|_| stty(&["-icanon", "-echo", "-isig"]).is_ok()) else {
+            let mut line = String::new();
+            return match io::stdin().lock().read_line(&mut line)? {
+                0 => Ok(None),
+                _ => Ok(Some(line.trim_end().to_string())),
+            };
+        };
+        let line = self.edit(prompt, complete);
+        stty(&[saved.trim()])?;
+
+        let line = line?;
+        if let Some(line) = &line {
+            if !line.trim().is_empty() && self.history.last() != Some(line) {
+                self.history.push(line.clone());
+            }
+        }
+        Ok(line)
+    }
+
+    fn edit(
+        &self,
+        prompt: &str,
+        complete: impl Fn(&str) -> Vec<String>,
+    ) -> io::Result<Option<String>> {
+        let redraw = |line: &str| {
+            print!("\r\x1b[2K{prompt}{line}");
+            io::stdout().flush()
+        };
+        let mut line = String::new();
+        // The bytes of a character that's only partly been read
+        let mut partial = Vec::new();
+        // How many lines back in the history the line came from
+        let mut back = 0;
+        let mut bytes = io::stdin().lock().bytes();
+        redraw(&line)?;
+        loop {
+            let Some(byte) = bytes.next().transpose()? else {
+                println!();
+                return Ok(None);
+            };
+            match byte {
+                b'\r' | b'\n' => {
+                    println!();
+                    return Ok(Some(line));
+                }
+                // Ctrl-D
+                4 if line.is_empty() => {
+                    println!();
+                    return Ok(None);
+                }
+                // Ctrl-C
+                3 => {
+                    println!("^C");
+                    line.clear();
+                    back = 0;
+                }
+                // Backspace
+                8 | 0x7f => {
+                    line.pop();
+                }
+                b'\t' => {
+                    let start = line.rfind(' ').map_or(0, |space| space + 1);
+                    let completions = complete(&line);
+                    match &completions[..] {
+                        [] => {}
+                        [completion] => {
+                            line.truncate(start);
+                            line.push_str(completion);
+                            // Directories can be finished further
+                            if !completion.ends_with('/') {
+                                line.push(' ');
+                            }
+                        }
+                        _ => {
+                            let common = common_prefix(&completions);
+                            if common.len() > line.len() - start {
+                                line.truncate(start);
+                                line.push_str(common);
+                            } else {
+                                println!("\n{}", completions.join("  "));
+                            }
+                        }
+                    }
+                }
+                // The up and down arrows are `ESC [ A` and `ESC [ B`
+                0x1b => {
+                    let (Some(Ok(b'[')), Some(Ok(key))) = (bytes.next(), bytes.next()) else {
+                        continue;
+                    };
+                    match key {
+                        b'A' if back < self.history.len() => back += 1,
+                        b'B' if back > 0 => back -= 1,
+                        _ => continue,
+                    }
+                    line = match back {
+                        0 => String::new(),
+                        back => self.history[self.history.len() - back].clone(),
+                    };
+                }
+                byte if byte >= 0x80 => {
+                    partial.push(byte);
+                    if let Ok(c) = std::str::from_utf8(&partial) {
+                        line.push_str(c);
+                        partial.clear();
+                    } else if partial.len() == 4 {
+                        partial.clear();
+                    }
+                }
+                byte if byte >= b' ' => line.push(byte as char),
+                _ => continue,
+            }
+            redraw(&line)?;
+        }
+    }
+}
+
+/// Runs `stty` on the terminal, returning what it printed
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The longest start that all of `words` have in common
+fn common_prefix(words: &[String]) -> &str {
+    let first = &words[0];
+    let mut length = first.len();
+    for word in &words[1..] {
+        length = first
+            .char_indices()
+            .zip(word.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(length.min(word.len()), |((index, _), _)| index.min(length));
+    }
+    &first[..length]
+}
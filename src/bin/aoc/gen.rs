@@ -0,0 +1,47 @@
+use advent_of_code_2022::generate::{self, DEFAULT_SIZE};
+use advent_of_code_2022::Day;
+
+use crate::Args;
+
+pub struct Options {
+    day: &'static Day,
+    seed: u64,
+    size: usize,
+}
+
+impl Options {
+    pub fn parse(args: &mut Args) -> Result<Self, String> {
+        let mut day = None;
+        let mut seed = 0;
+        let mut size = DEFAULT_SIZE;
+        while let Some(arg) = args.next() {
+            match arg {
+                "--day" | "-d" => day = Some(args.day(arg)?),
+                "--seed" => {
+                    let value = args.value(arg)?;
+                    seed = value
+                        .parse()
+                        .map_err(|_| format!("--seed must be a whole number, not `{value}`"))?;
+                }
+                "--size" => {
+                    let value = args.value(arg)?;
+                    size = value
+                        .parse()
+                        .map_err(|_| format!("--size must be a whole number, not `{value}`"))?;
+                }
+                other => return Err(format!("unexpected argument `{other}`")),
+            }
+        }
+        let day = day.ok_or("pass --day <N>")?;
+        Ok(Options { day, seed, size })
+    }
+}
+
+/// Prints a random input for the day
+pub fn gen(options: Options) -> Result<bool, String> {
+    print!(
+        "{}",
+        generate::generate(options.day, options.seed, options.size)
+    );
+    Ok(true)
+}
@@ -0,0 +1,200 @@
+mod bar;
+mod bench;
+mod editor;
+mod gen;
+mod params;
+mod repl;
+mod run;
+mod serve;
+mod snapshots;
+mod verify;
+
+use std::env;
+use std::process::ExitCode;
+
+use advent_of_code_2022::log::{self, Level};
+use advent_of_code_2022::{get_day, Day, Part};
+
+const USAGE: &str = "Usage:
+    aoc [-v|-vv|-vvv] <COMMAND> ...
+    aoc run --day <N> [--part <1|2>] [--input <PATH|->] [--format <text|json>]
+            [--param <dayN.NAME=VALUE>]... [--params <PATH>] [--jobs <N>]
+            [--timeout <SECONDS>] [--trace <PATH>] [--stream] [--checked]
+    aoc run --all [--format <text|json>] [--param ...] [--params <PATH>]
+            [--jobs <N>] [--timeout <SECONDS>] [--trace <PATH>] [--checked]
+    aoc params [--day <N>]
+    aoc gen --day <N> [--seed <S>] [--size <N>]
+    aoc verify [--day <N>] [--answers <PATH>]
+    aoc bench [--day <N>] [--part <1|2>] [--runs <N>] [--time <SECONDS>]
+              [--save <PATH>] [--baseline <PATH>] [--threshold <PERCENT>]
+    aoc serve [--host <HOST>] [--port <PORT>] [--max-body <BYTES>]
+              [--timeout <SECONDS>]
+    aoc snapshots [--accept]
+    aoc repl --day <N> [--input <PATH>] [--param <dayN.NAME=VALUE>]...
+             [--params <PATH>]
+
+Without --input, the bundled dayN.txt is used. Pass `-` to read from stdin.
+--format json prints one JSON object per day, with its answers, the runtime
+of each part in nanoseconds and any error.
+--jobs runs days, and the parts of a day, on that many threads (default: one
+per CPU). Days are still printed in order, followed by the total wall-clock
+and CPU time in text format.
+--timeout stops each part that runs for longer than that and reports it as
+timed out, which makes the run fail. When the output is a terminal, a
+progress bar shows how far the longer parts have got. Built with
+--features count-allocations, run also reports what each part allocated.
+--trace writes each step of the simulations in days 5, 9, 10, 11, 12 and 14,
+like crane moves or monkey throws, to a file as one JSON object per line.
+--stream solves days 1, 2, 3, 4, 6, 9 and 10 while reading their input, in
+one pass, without holding all of it in memory, so huge inputs can be piped in.
+--checked stops parsing or a part at the first overflow in the solutions'
+arithmetic, reporting where it happened and its operands, instead of
+wrapping or panicking. Building with --features wide-ints widens their
+32-bit and 16-bit integers to 64 bits, for inputs with bigger numbers.
+--param overrides one of a day's puzzle constants, like day15.row=10 for the
+example. --params reads them from a file with a [dayN] table per day, each
+followed by NAME = VALUE lines. params lists every day's parameters.
+gen prints a random input for a day, the same for the same seed (default 0).
+--size is roughly how many elves, lines, rows and so on (default 10).
+verify checks every day against the expected answers in answers.toml.
+bench times parsing and each part on the bundled inputs, reporting min, median
+and p95. Results can be saved as JSON and later passed as a --baseline, which
+fails if a median got slower by more than --threshold (default 10%).
+serve answers `POST /day/<N>` requests, with the input as the body, with the
+same JSON as --format json, on 127.0.0.1:8080 by default. Inputs can be at
+most --max-body bytes (default 1 MiB), and each part of the day, as well as
+reading the request, gets --timeout seconds (default 10). Days with a part
+that timed out get a 503 response, and inputs that can't be parsed a 422.
+snapshots shows how the text the tests render, like day 10's CRT, differs
+from the snapshots they check it against, saved when a snapshot test fails.
+--accept replaces the snapshots with it. Running the tests with
+UPDATE_SNAPSHOTS=1 writes every snapshot instead.
+repl parses a day's input once and then answers queries about it, one per
+line, like `size /a/e` for day 7, `steps X Y` for day 12 or `covers X Y` for
+day 15. help lists the day's queries, tab finishes a query or its argument,
+and part1 and part2 solve the day.
+
+Diagnostics from the solutions are off by default. Each -v (or --verbose)
+logs more detail to stderr: info, then debug, then trace. AOC_LOG sets the
+level for all days or single ones, e.g. AOC_LOG=info,day13=trace.";
+
+fn main() -> ExitCode {
+    if let Err(message) = log::init_from_env() {
+        eprintln!("error: {message}");
+        return ExitCode::FAILURE;
+    }
+
+    // Verbosity can be given anywhere on the command line
+    let mut verbosity = 0;
+    let args: Vec<String> = env::args()
+        .skip(1)
+        .filter(|arg| match arg.as_str() {
+            "--verbose" => {
+                verbosity += 1;
+                false
+            }
+            _ if arg
+                .strip_prefix('-')
+                .is_some_and(|flags| !flags.is_empty() && flags.bytes().all(|c| c == b'v')) =>
+            {
+                verbosity += arg.len() - 1;
+                false
+            }
+            _ => true,
+        })
+        .collect();
+    if let Some(level) = Level::from_verbosity(verbosity) {
+        log::set_level(level);
+    }
+
+    let mut args = Args::new(&args);
+    let command = match args.next() {
+        Some("run") => run::Options::parse(&mut args).map(Command::Run),
+        Some("verify") => verify::Options::parse(&mut args).map(Command::Verify),
+        Some("bench") => bench::Options::parse(&mut args).map(Command::Bench),
+        Some("params") => params::Options::parse(&mut args).map(Command::Params),
+        Some("gen") => gen::Options::parse(&mut args).map(Command::Gen),
+        Some("serve") => serve::Options::parse(&mut args).map(Command::Serve),
+        Some("snapshots") => snapshots::Options::parse(&mut args).map(Command::Snapshots),
+        Some("repl") => repl::Options::parse(&mut args).map(Command::Repl),
+        Some(other) => Err(format!("unknown command `{other}`")),
+        None => Err("missing command".to_string()),
+    };
+    let command = match command {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match command {
+        Command::Run(options) => run::run(options),
+        Command::Verify(options) => verify::verify(options),
+        Command::Bench(options) => bench::bench(options),
+        Command::Params(options) => params::list(options),
+        Command::Gen(options) => gen::gen(options),
+        Command::Serve(options) => serve::serve(options),
+        Command::Snapshots(options) => snapshots::snapshots(options),
+        Command::Repl(options) => repl::repl(options),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+enum Command {
+    Run(run::Options),
+    Verify(verify::Options),
+    Bench(bench::Options),
+    Params(params::Options),
+    Gen(gen::Options),
+    Serve(serve::Options),
+    Snapshots(snapshots::Options),
+    Repl(repl::Options),
+}
+
+/// The remaining command line arguments
+pub struct Args<'a> {
+    args: std::slice::Iter<'a, String>,
+}
+
+impl<'a> Args<'a> {
+    fn new(args: &'a [String]) -> Self {
+        Args { args: args.iter() }
+    }
+
+    /// The value that must follow `flag`
+    pub fn value(&mut self, flag: &str) -> Result<&'a str, String> {
+        self.next().ok_or_else(|| format!("{flag} needs a value"))
+    }
+
+    pub fn day(&mut self, flag: &str) -> Result<&'static Day, String> {
+        let day = self.value(flag)?;
+        day.parse()
+            .ok()
+            .and_then(get_day)
+            .ok_or_else(|| format!("there is no solution for day `{day}`"))
+    }
+
+    pub fn part(&mut self, flag: &str) -> Result<Part, String> {
+        match self.value(flag)? {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            other => Err(format!("part must be 1 or 2, not `{other}`")),
+        }
+    }
+}
+
+impl<'a> Iterator for Args<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.args.next().map(String::as_str)
+    }
+}
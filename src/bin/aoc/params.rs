@@ -0,0 +1,39 @@
+use advent_of_code_2022::{Day, DAYS};
+
+use crate::Args;
+
+pub struct Options {
+    days: Vec<&'static Day>,
+}
+
+impl Options {
+    pub fn parse(args: &mut Args) -> Result<Self, String> {
+        let mut days = Vec::new();
+        while let Some(arg) = args.next() {
+            match arg {
+                "--day" | "-d" => days.push(args.day(arg)?),
+                other => return Err(format!("unexpected argument `{other}`")),
+            }
+        }
+        if days.is_empty() {
+            days = DAYS.iter().filter(|day| !day.params.is_empty()).collect();
+        }
+        Ok(Options { days })
+    }
+}
+
+/// Prints each day's parameters with their defaults, in the form `--param`
+/// takes
+pub fn list(options: Options) -> Result<bool, String> {
+    for day in options.days {
+        if day.params.is_empty() {
+            println!("Day {} has no parameters", day.number);
+            continue;
+        }
+        for param in day.params {
+            let setting = format!("day{}.{}={}", day.number, param.name, param.default);
+            println!("{setting:<32} {}", param.description);
+        }
+    }
+    Ok(true)
+}
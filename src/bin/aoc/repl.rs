@@ -0,0 +1,149 @@
+use std::fs;
+use std::time::Instant;
+
+use advent_of_code_2022::input::InputSource;
+use advent_of_code_2022::params::Params;
+use advent_of_code_2022::{Context, Day, Parsed, Part};
+
+use crate::editor::Editor;
+use crate::Args;
+
+/// What can be typed besides the day's own queries
+const COMMANDS: &[(&str, &str)] = &[
+    ("part1", "solves part 1"),
+    ("part2", "solves part 2"),
+    ("help", "lists what can be typed"),
+    ("quit", "ends the session"),
+];
+
+pub struct Options {
+    day: &'static Day,
+    input: InputSource,
+    params: Params,
+}
+
+impl Options {
+    pub fn parse(args: &mut Args) -> Result<Self, String> {
+        let mut day = None;
+        let mut input = InputSource::Bundled;
+        let mut params = Params::new();
+        while let Some(arg) = args.next() {
+            match arg {
+                "--day" | "-d" => day = Some(args.day(arg)?),
+                "--input" | "-i" => input = InputSource::from_arg(args.value(arg)?),
+                "--param" => params.set_from_arg(args.value(arg)?)?,
+                "--params" => {
+                    let path = args.value(arg)?;
+                    let text = fs::read_to_string(path)
+                        .map_err(|err| format!("could not read `{path}`: {err}"))?;
+                    params
+                        .extend_from_file(&text)
+                        .map_err(|err| format!("{path}: {err}"))?;
+                }
+                other => return Err(format!("unexpected argument `{other}`")),
+            }
+        }
+        let day = day.ok_or("pass --day <N>")?;
+        if input == InputSource::Stdin {
+            return Err("the queries are read from stdin, so --input can't be `-`".to_string());
+        }
+        Ok(Options { day, input, params })
+    }
+}
+
+/// Parses the day's input once, then answers queries about it until the
+/// input ends. Returns whether every query could be answered.
+pub fn repl(options: Options) -> Result<bool, String> {
+    let day = options.day;
+    let input = options.input.load(day).map_err(|error| error.to_string())?;
+    let start = Instant::now();
+    let parsed = day.parse(&input).map_err(|error| error.to_string())?;
+    println!(
+        "Parsed day {} in {:.2?}, `help` lists what can be asked",
+        day.number,
+        start.elapsed()
+    );
+
+    let context = Context::new(day, &options.params);
+    let mut session = day.session();
+    let prompt = format!("day{}> ", day.number);
+    let mut editor = Editor::new();
+    let mut answered = true;
+    while let Some(line) = editor
+        .read_line(&prompt, |line| complete(day, &parsed, line))
+        .map_err(|err| format!("could not read the query: {err}"))?
+    {
+        let mut words = line.split_whitespace();
+        let Some(name) = words.next() else {
+            continue;
+        };
+        let args: Vec<&str> = words.collect();
+        match (name, &args[..]) {
+            ("quit" | "exit", []) => break,
+            ("help", []) => help(day),
+            ("part1", []) => solve(day, Part::One, &parsed, &context),
+            ("part2", []) => solve(day, Part::Two, &parsed, &context),
+            _ => match day.query(&parsed, &mut session, name, &args) {
+                Ok(answer) => println!("{answer}"),
+                Err(message) => {
+                    eprintln!("error: {message}");
+                    answered = false;
+                }
+            },
+        }
+    }
+    Ok(answered)
+}
+
+fn help(day: &Day) {
+    let usages: Vec<_> = day
+        .queries
+        .iter()
+        .map(|query| (query.usage(), query.help))
+        .chain(
+            COMMANDS
+                .iter()
+                .map(|&(name, help)| (name.to_string(), help)),
+        )
+        .collect();
+    let width = usages
+        .iter()
+        .map(|(usage, _)| usage.len())
+        .max()
+        .unwrap_or(0);
+    for (usage, help) in usages {
+        println!("  {usage:width$}  {help}");
+    }
+}
+
+fn solve(day: &Day, part: Part, parsed: &Parsed, context: &Context) {
+    let start = Instant::now();
+    match day.solve(part, parsed, context) {
+        Some(Ok(answer)) => println!("{answer} ({:.2?})", start.elapsed()),
+        Some(Err(error)) => eprintln!("error: {error}"),
+        None => println!("not solved yet"),
+    }
+}
+
+/// What the last word of `line` can be finished to: a query or command for
+/// the first word, and the query's own completions for its arguments
+fn complete(day: &Day, parsed: &Parsed, line: &str) -> Vec<String> {
+    let mut words: Vec<&str> = line.split(' ').collect();
+    let prefix = words.pop().unwrap_or_default();
+    words.retain(|word| !word.is_empty());
+    match words[..] {
+        [] => {
+            let mut names: Vec<String> = day
+                .queries
+                .iter()
+                .map(|query| query.name)
+                .chain(COMMANDS.iter().map(|&(name, _)| name))
+                .filter(|name| name.starts_with(prefix))
+                .map(str::to_string)
+                .collect();
+            names.sort();
+            names
+        }
+        [name, ref args @ ..] => day.complete(parsed, name, args.len(), prefix),
+    }
+}
@@ -0,0 +1,251 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, IsTerminal};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use advent_of_code_2022::input::InputSource;
+use advent_of_code_2022::pool;
+use advent_of_code_2022::runner::{self, DayResult, Job, Settings};
+use advent_of_code_2022::trace::JsonLines;
+use advent_of_code_2022::{Day, DAYS};
+
+use crate::bar::Bar;
+use crate::Args;
+
+pub struct Options {
+    days: Vec<&'static Day>,
+    input: InputSource,
+    format: Format,
+    settings: Settings,
+    /// Where to write the simulations' events
+    trace: Option<PathBuf>,
+    /// Whether to solve the day while reading its input
+    stream: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    /// One JSON object per day, each on its own line
+    Json,
+}
+
+impl Options {
+    pub fn parse(args: &mut Args) -> Result<Self, String> {
+        let mut days = Vec::new();
+        let mut input = InputSource::Bundled;
+        let mut format = Format::Text;
+        let mut settings = Settings {
+            threads: pool::default_threads(),
+            ..Settings::default()
+        };
+        let mut trace = None;
+        let mut stream = false;
+        let mut all = false;
+        while let Some(arg) = args.next() {
+            match arg {
+                "--all" => all = true,
+                "--day" | "-d" => days.push(args.day(arg)?),
+                "--part" | "-p" => settings.parts = vec![args.part(arg)?],
+                "--input" | "-i" => input = InputSource::from_arg(args.value(arg)?),
+                "--format" => {
+                    format = match args.value(arg)? {
+                        "text" => Format::Text,
+                        "json" => Format::Json,
+                        other => {
+                            return Err(format!("--format must be text or json, not `{other}`"))
+                        }
+                    }
+                }
+                "--param" => settings.params.set_from_arg(args.value(arg)?)?,
+                "--params" => {
+                    let path = args.value(arg)?;
+                    let text = fs::read_to_string(path)
+                        .map_err(|err| format!("could not read `{path}`: {err}"))?;
+                    settings
+                        .params
+                        .extend_from_file(&text)
+                        .map_err(|err| format!("{path}: {err}"))?;
+                }
+                "--jobs" | "-j" => {
+                    let jobs = args.value(arg)?;
+                    settings.threads =
+                        jobs.parse().ok().filter(|&jobs| jobs > 0).ok_or_else(|| {
+                            format!("--jobs must be a positive number, not `{jobs}`")
+                        })?;
+                }
+                "--timeout" => {
+                    let timeout = args.value(arg)?;
+                    settings.timeout = Some(
+                        timeout
+                            .parse()
+                            .ok()
+                            .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                            .ok_or_else(|| {
+                                format!("--timeout must be a number of seconds, not `{timeout}`")
+                            })?,
+                    );
+                }
+                "--trace" => trace = Some(PathBuf::from(args.value(arg)?)),
+                "--stream" => stream = true,
+                "--checked" => settings.checked = true,
+                other => return Err(format!("unexpected argument `{other}`")),
+            }
+        }
+
+        if all {
+            if !days.is_empty() {
+                return Err("--all can't be combined with --day".to_string());
+            }
+            days = DAYS.iter().collect();
+        } else if days.is_empty() {
+            return Err("pass either --day <N> or --all".to_string());
+        }
+        if input != InputSource::Bundled && days.len() > 1 {
+            return Err("--input can only be used when running a single day".to_string());
+        }
+        if stream {
+            match days[..] {
+                [day] if day.can_stream() => {}
+                [day] => return Err(format!("day {} can't be streamed", day.number)),
+                _ => return Err("--stream can only be used when running a single day".to_string()),
+            }
+            if settings.timeout.is_some() || trace.is_some() {
+                return Err("--stream can't be combined with --timeout or --trace".to_string());
+            }
+        }
+
+        Ok(Options {
+            days,
+            input,
+            format,
+            settings,
+            trace,
+            stream,
+        })
+    }
+}
+
+/// Returns whether every day ran without an error
+pub fn run(mut options: Options) -> Result<bool, String> {
+    if options.stream {
+        return stream(options);
+    }
+    let jobs: Vec<_> = options
+        .days
+        .iter()
+        .map(|&day| Job {
+            day,
+            input: options.input.load(day).map_err(|error| error.to_string()),
+        })
+        .collect();
+
+    let trace = match &options.trace {
+        Some(path) => {
+            let file = File::create(path)
+                .map_err(|err| format!("could not create `{}`: {err}", path.display()))?;
+            Some(Arc::new(JsonLines::new(BufWriter::new(file))))
+        }
+        None => None,
+    };
+    if let Some(trace) = &trace {
+        options.settings.trace = Some(trace.clone());
+    }
+
+    let bar = io::stdout().is_terminal().then(|| Arc::new(Bar::new()));
+    if let Some(bar) = &bar {
+        options.settings.progress = Some(bar.clone());
+    }
+    let print = |print: &dyn Fn()| match &bar {
+        Some(bar) => bar.suspend(print),
+        None => print(),
+    };
+
+    let mut all_ok = true;
+    let summary = runner::run_days(&jobs, &options.settings, |result| {
+        all_ok &= result.error.is_none()
+            && !result.panicked
+            && !result
+                .parts
+                .iter()
+                .any(|part| part.timed_out || part.overflow.is_some() || part.error.is_some());
+        print(&|| match options.format {
+            Format::Text => print_text(&result),
+            Format::Json => println!("{}", result.to_json()),
+        });
+    });
+    print(&|| {
+        if options.format == Format::Text {
+            println!("Ran {summary}");
+        }
+    });
+    if let (Some(trace), Some(path)) = (trace, &options.trace) {
+        trace
+            .flush()
+            .map_err(|err| format!("could not write `{}`: {err}", path.display()))?;
+    }
+    Ok(all_ok)
+}
+
+/// Solves the single day in `options` while reading its input
+fn stream(options: Options) -> Result<bool, String> {
+    let day = options.days[0];
+    let result = match options.input.open(day) {
+        Ok(mut input) => runner::stream_day(day, &mut input, &options.settings),
+        Err(error) => DayResult::failed(day.number, error),
+    };
+    match options.format {
+        Format::Text => print_text(&result),
+        Format::Json => println!("{}", result.to_json()),
+    }
+    Ok(result.error.is_none() && result.parts.iter().all(|part| part.error.is_none()))
+}
+
+fn print_text(result: &DayResult) {
+    println!("Day {}", result.day);
+    if let Some(error) = &result.error {
+        eprintln!("error: {error}");
+    }
+    if let Some(parse_time) = result.parse_time {
+        println!("  Parsed in {parse_time:.2?}");
+    }
+
+    for part_result in &result.parts {
+        let (part, elapsed) = (part_result.part, part_result.time);
+        if part_result.timed_out {
+            println!("  Part {part}: timed out after {elapsed:.2?}");
+            continue;
+        }
+        if let Some(overflow) = &part_result.overflow {
+            println!("  Part {part}: {overflow}");
+            continue;
+        }
+        if let Some(message) = &part_result.panic {
+            println!("  Part {part}: panicked: {message}");
+            continue;
+        }
+        if let Some(error) = &part_result.error {
+            println!("  Part {part}: error: {error}");
+            continue;
+        }
+        let Some(answer) = &part_result.answer else {
+            println!("  Part {part}: not solved yet");
+            continue;
+        };
+
+        let stats = match &part_result.allocations {
+            Some(allocations) => format!("{elapsed:.2?}, {allocations}"),
+            None => format!("{elapsed:.2?}"),
+        };
+        let answer = answer.to_string();
+        if answer.contains('\n') {
+            println!("  Part {part} ({stats}):");
+            for line in answer.lines() {
+                println!("    {line}");
+            }
+        } else {
+            println!("  Part {part}: {answer} ({stats})");
+        }
+    }
+}
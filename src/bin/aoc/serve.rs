@@ -0,0 +1,79 @@
+use std::net::TcpListener;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use advent_of_code_2022::http::{self, Limits};
+
+use crate::Args;
+
+pub struct Options {
+    address: String,
+    limits: Limits,
+}
+
+impl Options {
+    pub fn parse(args: &mut Args) -> Result<Self, String> {
+        let mut host = "127.0.0.1";
+        let mut port = 8080;
+        let mut limits = Limits::default();
+        while let Some(arg) = args.next() {
+            match arg {
+                "--host" => host = args.value(arg)?,
+                "--port" => {
+                    let value = args.value(arg)?;
+                    port = value
+                        .parse::<u16>()
+                        .map_err(|_| format!("--port must be a port number, not `{value}`"))?;
+                }
+                "--max-body" => {
+                    let value = args.value(arg)?;
+                    limits.max_body = value.parse().map_err(|_| {
+                        format!("--max-body must be a number of bytes, not `{value}`")
+                    })?;
+                }
+                "--timeout" => {
+                    let timeout = args.value(arg)?;
+                    limits.timeout = timeout
+                        .parse()
+                        .ok()
+                        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                        .filter(|timeout| !timeout.is_zero())
+                        .ok_or_else(|| {
+                            format!("--timeout must be a number of seconds, not `{timeout}`")
+                        })?;
+                }
+                other => return Err(format!("unexpected argument `{other}`")),
+            }
+        }
+        Ok(Options {
+            address: format!("{host}:{port}"),
+            limits,
+        })
+    }
+}
+
+/// Answers requests until the process is stopped, each on its own thread
+pub fn serve(options: Options) -> Result<bool, String> {
+    let listener = TcpListener::bind(&options.address)
+        .map_err(|err| format!("could not listen on {}: {err}", options.address))?;
+    let address = listener.local_addr().map_err(|err| err.to_string())?;
+    eprintln!("Listening on http://{address}");
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                eprintln!("error: could not accept a connection: {err}");
+                continue;
+            }
+        };
+        let limits = options.limits;
+        thread::spawn(move || {
+            let start = Instant::now();
+            match http::respond(&stream, &limits) {
+                Ok(summary) => eprintln!("{summary} in {:.2?}", start.elapsed()),
+                Err(err) => eprintln!("error: could not respond: {err}"),
+            }
+        });
+    }
+    Ok(true)
+}
@@ -0,0 +1,52 @@
+use advent_of_code_2022::snapshot;
+
+use crate::Args;
+
+pub struct Options {
+    accept: bool,
+}
+
+impl Options {
+    pub fn parse(args: &mut Args) -> Result<Self, String> {
+        let mut accept = false;
+        for arg in args.by_ref() {
+            match arg {
+                "--accept" => accept = true,
+                other => return Err(format!("unexpected argument `{other}`")),
+            }
+        }
+        Ok(Options { accept })
+    }
+}
+
+/// Shows how each rendering that's waiting to be accepted differs from its
+/// snapshot, accepting them with `--accept`
+pub fn snapshots(options: Options) -> Result<bool, String> {
+    let pending =
+        snapshot::pending().map_err(|err| format!("could not read {}: {err}", snapshot::DIR))?;
+    if pending.is_empty() {
+        println!("No new snapshots");
+        return Ok(true);
+    }
+    for pending in &pending {
+        match &pending.old {
+            Some(old) => println!("{}:\n{}", pending.name, snapshot::diff(old, &pending.new)),
+            None => println!("{} (new):\n{}", pending.name, pending.new),
+        }
+        if options.accept {
+            pending
+                .accept()
+                .map_err(|err| format!("could not accept {}: {err}", pending.name))?;
+        }
+    }
+    let count = match pending.len() {
+        1 => "1 snapshot".to_string(),
+        count => format!("{count} snapshots"),
+    };
+    if options.accept {
+        println!("Accepted {count}");
+    } else {
+        println!("{count} to review, accept with --accept");
+    }
+    Ok(true)
+}
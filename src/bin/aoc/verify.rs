@@ -0,0 +1,120 @@
+use std::fs;
+use std::path::PathBuf;
+
+use advent_of_code_2022::answers::{Answers, Verdict, ANSWERS};
+use advent_of_code_2022::{Day, Part, DAYS};
+
+use crate::Args;
+
+pub struct Options {
+    days: Vec<&'static Day>,
+    /// Defaults to the answers.toml built into the binary
+    answers: Option<PathBuf>,
+}
+
+impl Options {
+    pub fn parse(args: &mut Args) -> Result<Self, String> {
+        let mut days = Vec::new();
+        let mut answers = None;
+        while let Some(arg) = args.next() {
+            match arg {
+                "--day" | "-d" => days.push(args.day(arg)?),
+                "--answers" => answers = Some(PathBuf::from(args.value(arg)?)),
+                other => return Err(format!("unexpected argument `{other}`")),
+            }
+        }
+        if days.is_empty() {
+            days = DAYS.iter().collect();
+        }
+        Ok(Options { days, answers })
+    }
+}
+
+/// Checks each day's bundled input against its expected answers. Returns
+/// `false` if any answer was wrong; missing answers are only reported.
+pub fn verify(options: Options) -> Result<bool, String> {
+    let (answers, answers_path) = match &options.answers {
+        Some(path) => {
+            let text = fs::read_to_string(path)
+                .map_err(|err| format!("could not read `{}`: {err}", path.display()))?;
+            (text, path.display().to_string())
+        }
+        None => (ANSWERS.to_string(), "answers.toml".to_string()),
+    };
+    let answers = Answers::parse(&answers).map_err(|err| format!("{answers_path}: {err}"))?;
+
+    let mut correct = 0;
+    let mut wrong = Vec::new();
+    let mut missing = Vec::new();
+
+    for day in options.days {
+        println!("Day {}", day.number);
+        let parsed = match day.parse(day.input) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                println!("  error: {error}");
+                wrong.extend(Part::ALL.map(|part| (day.number, part)));
+                continue;
+            }
+        };
+
+        for part in Part::ALL {
+            match answers.verify(day, &parsed, part) {
+                Verdict::Correct => {
+                    println!("  Part {part}: ok");
+                    correct += 1;
+                }
+                Verdict::Wrong { expected, actual } => {
+                    println!("  Part {part}: WRONG");
+                    print_answer("expected", &expected);
+                    print_answer("got", &actual.to_string());
+                    wrong.push((day.number, part));
+                }
+                Verdict::Missing {
+                    actual: Some(actual),
+                } => {
+                    println!("  Part {part}: missing from {answers_path}");
+                    print_answer("got", &actual.to_string());
+                    missing.push((day.number, part));
+                }
+                Verdict::Missing { actual: None } => {
+                    println!("  Part {part}: missing, not solved yet");
+                    missing.push((day.number, part));
+                }
+                Verdict::Failed { error } => {
+                    println!("  Part {part}: FAILED: {error}");
+                    wrong.push((day.number, part));
+                }
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{correct} correct, {} wrong, {} missing",
+        wrong.len(),
+        missing.len()
+    );
+    for (label, parts) in [("Wrong", &wrong), ("Missing", &missing)] {
+        if !parts.is_empty() {
+            let parts: Vec<String> = parts
+                .iter()
+                .map(|(day, part)| format!("day {day} part {part}"))
+                .collect();
+            println!("{label}: {}", parts.join(", "));
+        }
+    }
+
+    Ok(wrong.is_empty())
+}
+
+fn print_answer(label: &str, answer: &str) {
+    if answer.contains('\n') {
+        println!("    {label}:");
+        for line in answer.lines() {
+            println!("      {line}");
+        }
+    } else {
+        println!("    {label}: {answer}");
+    }
+}
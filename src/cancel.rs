@@ -0,0 +1,140 @@
+//! Cooperative cancellation, so a part that runs out of time can stop
+//! instead of holding up the rest of a run.
+//!
+//! Long loops in the solutions call [`Cancellation::check`] once per
+//! iteration. When the cancellation has been cancelled, or its deadline has
+//! passed, `check` unwinds out of the part with a [`Cancelled`] payload,
+//! which [`catch`] turns back into an error. No panic message is
+//! printed, since it doesn't go through the panic hook.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug)]
+pub struct Cancellation {
+    /// `None` for a cancellation that can't be cancelled by hand
+    cancelled: Option<Arc<AtomicBool>>,
+    deadline: Option<Instant>,
+}
+
+/// The payload [`Cancellation::check`] unwinds with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cancelled;
+
+/// What [`Context::with_defaults`](crate::Context::with_defaults) and
+/// [`Context::new`](crate::Context::new) check
+pub static NEVER: Cancellation = Cancellation::never();
+
+impl Cancellation {
+    /// A cancellation that never happens, for when there's no time limit
+    pub const fn never() -> Self {
+        Cancellation {
+            cancelled: None,
+            deadline: None,
+        }
+    }
+
+    /// A cancellation that only happens through [`cancel`](Self::cancel)
+    pub fn new() -> Self {
+        Cancellation {
+            cancelled: Some(Arc::new(AtomicBool::new(false))),
+            deadline: None,
+        }
+    }
+
+    /// A cancellation that also happens once `timeout` has passed from now
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Cancellation {
+            deadline: Instant::now().checked_add(timeout),
+            ..Cancellation::new()
+        }
+    }
+
+    /// Cancels this and every clone of it
+    pub fn cancel(&self) {
+        if let Some(cancelled) = &self.cancelled {
+            cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        if self
+            .cancelled
+            .as_ref()
+            .is_some_and(|cancelled| cancelled.load(Ordering::Relaxed))
+        {
+            return true;
+        }
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            self.cancel();
+            return true;
+        }
+        false
+    }
+
+    /// Stops the current part if it has been cancelled
+    pub fn check(&self) {
+        if self.is_cancelled() {
+            panic::resume_unwind(Box::new(Cancelled));
+        }
+    }
+}
+
+impl Default for Cancellation {
+    fn default() -> Self {
+        Cancellation::new()
+    }
+}
+
+/// Runs `f`, returning [`Cancelled`] if it stopped at a
+/// [`check`](Cancellation::check). Other panics carry on unwinding.
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, Cancelled> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(value) => Ok(value),
+        Err(payload) if payload.is::<Cancelled>() => Err(Cancelled),
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_forever(cancellation: &Cancellation) -> u64 {
+        let mut count = 0u64;
+        loop {
+            cancellation.check();
+            count = count.wrapping_add(1);
+        }
+    }
+
+    #[test]
+    fn timing_out() {
+        let cancellation = Cancellation::with_timeout(Duration::from_millis(10));
+        assert_eq!(catch(|| count_forever(&cancellation)), Err(Cancelled));
+        assert!(cancellation.is_cancelled());
+    }
+
+    #[test]
+    fn cancelling_clones() {
+        let cancellation = Cancellation::new();
+        let clone = cancellation.clone();
+        assert_eq!(catch(|| 5), Ok(5));
+        cancellation.cancel();
+        assert_eq!(catch(|| clone.check()), Err(Cancelled));
+
+        NEVER.cancel();
+        assert!(!NEVER.is_cancelled());
+    }
+
+    #[test]
+    #[should_panic(expected = "not a cancellation")]
+    fn other_panics() {
+        let _ = catch(|| panic!("not a cancellation"));
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+
+use crate::overflow::{self, Unsigned};
+use crate::parse::{self, Line, ParseError};
+use crate::stream::{self, StreamError, Streaming};
+use crate::{Answer, Context, Part, Solution};
+
+pub const INPUT: &str = include_str!("../inputs/day1.txt");
+
+pub struct Day1;
+
+type Calories = Unsigned;
+
+impl Solution for Day1 {
+    /// The total calories carried by each elf
+    type Parsed = Vec<Calories>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut elves = vec![0];
+        for line in parse::lines(input) {
+            match parse_line(line)? {
+                Some(calories) => {
+                    let elf = elves.last_mut().unwrap();
+                    *elf = overflow::add(*elf, calories);
+                }
+                None => elves.push(0),
+            }
+        }
+        Ok(elves)
+    }
+
+    fn part1(elves: &Self::Parsed, _context: &Context) -> Result<Answer, String> {
+        Ok(most_calories(elves).into())
+    }
+
+    fn part2(elves: &Self::Parsed, _context: &Context) -> Option<Result<Answer, String>> {
+        Some(Ok(top_three_calories(elves).into()))
+    }
+}
+
+impl Streaming for Day1 {
+    fn stream(
+        input: &mut dyn BufRead,
+        parts: &[Part],
+        _context: &Context,
+    ) -> Result<Vec<Result<Answer, String>>, StreamError> {
+        // The most calories carried by any three elves, most first
+        let mut top_three = [0; 3];
+        let mut elf = 0;
+        stream::for_each_line(input, |line| {
+            match parse_line(line)? {
+                Some(calories) => elf = overflow::add(elf, calories),
+                None => add_to_top_three(&mut top_three, std::mem::take(&mut elf)),
+            }
+            Ok(())
+        })?;
+        add_to_top_three(&mut top_three, elf);
+        Ok(parts
+            .iter()
+            .map(|part| match part {
+                Part::One => Ok(top_three[0].into()),
+                Part::Two => Ok(top_three.into_iter().fold(0, overflow::add).into()),
+            })
+            .collect())
+    }
+}
+
+/// The calories on a line, or `None` for the blank line between elves
+fn parse_line(mut line: Line) -> Result<Option<Calories>, ParseError> {
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let calories = line.number()?;
+    line.finish()?;
+    Ok(Some(calories))
+}
+
+fn add_to_top_three(top_three: &mut [Calories; 3], calories: Calories) {
+    if let Some(place) = top_three.iter().position(|&top| calories > top) {
+        top_three[place..].rotate_right(1);
+        top_three[place] = calories;
+    }
+}
+
+pub fn most_calories(elves: &[Calories]) -> Calories {
+    elves.iter().copied().max().unwrap()
+}
+
+/// The calories carried by the three elves carrying the most, or by all of
+/// them if there are fewer than three
+pub fn top_three_calories(elves: &[Calories]) -> Calories {
+    let mut elves: BinaryHeap<Calories> = elves.iter().copied().collect();
+    let mut total = 0;
+    // Not a fold, so overflows are reported here rather than in the standard
+    // library
+    for calories in (0..3).map_while(|_| elves.pop()) {
+        total = overflow::add(total, calories);
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fewer_than_three_elves() {
+        let day = crate::get_day(1).unwrap();
+        let context = Context::with_defaults(day);
+        for (input, total) in [("1000\n2000", 3000), ("1000\n2000\n\n4000", 7000)] {
+            let parsed = day.parse(input).unwrap();
+            assert_eq!(
+                day.solve(Part::Two, &parsed, &context),
+                Some(Ok(Answer::Number(total)))
+            );
+            let streamed = day
+                .stream(&mut input.as_bytes(), &[Part::Two], &context)
+                .unwrap()
+                .unwrap();
+            assert_eq!(streamed, [Ok(Answer::Number(total))]);
+        }
+    }
+}
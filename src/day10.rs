@@ -0,0 +1,262 @@
+use std::io::BufRead;
+use std::iter::once;
+
+use crate::json::Json;
+use crate::params::Param;
+use crate::parse::{self, Line, ParseError};
+use crate::stream::{self, StreamError, Streaming};
+use crate::trace::{self, Event};
+use crate::{Answer, Context, Part, Solution};
+
+pub const INPUT: &str = include_str!("../inputs/day10.txt");
+
+pub struct Day10;
+
+/// The widest CRT, far wider than any picture worth drawing
+const MAX_CRT_WIDTH: i64 = 10_000;
+
+impl Solution for Day10 {
+    /// How much is added to the X register at the end of each cycle
+    type Parsed = Vec<i32>;
+
+    const PARAMS: &'static [Param] =
+        &[Param::new("crt_width", 40, "pixels in each row of the CRT")
+            .at_least(1)
+            .at_most(MAX_CRT_WIDTH)];
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_instructions(input)
+    }
+
+    fn part1(program: &Self::Parsed, _context: &Context) -> Result<Answer, String> {
+        Ok(interesting_signal_strengths(program).into())
+    }
+
+    fn part2(program: &Self::Parsed, context: &Context) -> Option<Result<Answer, String>> {
+        Some(Ok(to_crt_string(program, context.param("crt_width")).into()))
+    }
+}
+
+impl Streaming for Day10 {
+    /// The CRT's picture for part 2 still grows with the program, since
+    /// that's the answer
+    fn stream(
+        input: &mut dyn BufRead,
+        parts: &[Part],
+        context: &Context,
+    ) -> Result<Vec<Result<Answer, String>>, StreamError> {
+        let mut crt = parts
+            .contains(&Part::Two)
+            .then(|| Crt::new(context.param("crt_width")));
+        let mut x = 1;
+        let mut cycle = 0;
+        let mut strengths = 0;
+        let mut add_strength = |cycle: usize, x: i32| {
+            if INTERESTING_CYCLES.contains(&cycle) {
+                strengths += cycle as i32 * x;
+            }
+        };
+        stream::for_each_line(input, |line| {
+            let add = parse_instruction(line)?;
+            for add in once(0).chain(add) {
+                cycle += 1;
+                add_strength(cycle, x);
+                if let Some(crt) = &mut crt {
+                    crt.draw(x);
+                }
+                x += add;
+            }
+            Ok(())
+        })?;
+        // The register after the program finishes
+        add_strength(cycle + 1, x);
+        Ok(parts
+            .iter()
+            .map(|part| match part {
+                Part::One => Ok(strengths.into()),
+                Part::Two => Ok(crt.take().unwrap().picture.into()),
+            })
+            .collect())
+    }
+}
+
+/// The cycles, counting from 1, whose signal strengths are added up for
+/// part 1
+const INTERESTING_CYCLES: [usize; 6] = [20, 60, 100, 140, 180, 220];
+
+/// Only adds up the interesting cycles a program gets to, or to just after
+/// it finishes, like streaming does
+pub fn interesting_signal_strengths(program: &[i32]) -> i32 {
+    let strengths: Vec<_> = signal_strengths(program).collect();
+    INTERESTING_CYCLES
+        .iter()
+        .filter_map(|cycle| strengths.get(cycle - 1))
+        .sum()
+}
+
+fn parse_instructions(input: &str) -> Result<Vec<i32>, ParseError> {
+    let mut program = Vec::new();
+    for line in parse::lines(input) {
+        program.push(0);
+        program.extend(parse_instruction(line)?);
+    }
+    Ok(program)
+}
+
+/// What an `addx` adds to the X register, or `None` for a `noop`. Either
+/// way the instruction adds nothing at the end of its first cycle.
+fn parse_instruction(mut line: Line) -> Result<Option<i32>, ParseError> {
+    let add = if line.eat("noop") {
+        None
+    } else if line.eat("addx ") {
+        Some(line.number()?)
+    } else {
+        return Err(line.error("`noop` or `addx`"));
+    };
+    line.finish()?;
+    Ok(add)
+}
+
+/// The X register during a CPU cycle
+struct Cycle {
+    /// Counting from 1, as in the puzzle
+    cycle: usize,
+    x: i32,
+}
+
+impl Event for Cycle {
+    fn name(&self) -> &'static str {
+        "cycle"
+    }
+
+    fn fields(&self, object: Json) -> Json {
+        object.with("cycle", self.cycle).with("x", self.x)
+    }
+}
+
+fn get_cycles(program: &[i32]) -> impl Iterator<Item = i32> + '_ {
+    let mut x = 1;
+    once(1).chain(program.iter().map(move |add| {
+        let after = x + add;
+        x = after;
+        after
+    }))
+}
+
+pub fn signal_strengths(program: &[i32]) -> impl Iterator<Item = i32> + '_ {
+    get_cycles(program).enumerate().map(|(cycle, x)| {
+        trace::emit(|| Cycle {
+            cycle: cycle + 1,
+            x,
+        });
+        (cycle as i32 + 1) * x
+    })
+}
+
+pub fn to_crt_string(program: &[i32], width: usize) -> String {
+    let mut crt = Crt::new(width);
+    // The last value is the register after the program finishes, which
+    // isn't drawn, so only take one value per instruction cycle
+    for (cycle, x) in get_cycles(program).take(program.len()).enumerate() {
+        trace::emit(|| Cycle {
+            cycle: cycle + 1,
+            x,
+        });
+        crt.draw(x);
+    }
+    crt.picture
+}
+
+/// The CRT's picture so far, drawn one pixel per cycle
+struct Crt {
+    width: usize,
+    pixels: usize,
+    /// A row of `#`s and `.`s per line
+    picture: String,
+}
+
+impl Crt {
+    fn new(width: usize) -> Self {
+        Crt {
+            width,
+            pixels: 0,
+            picture: String::new(),
+        }
+    }
+
+    /// Draws the next pixel, which is lit if the sprite centred on `x`
+    /// covers it
+    fn draw(&mut self, x: i32) {
+        let column = self.pixels % self.width;
+        if self.pixels > 0 && column == 0 {
+            self.picture.push('\n');
+        }
+        let lit = (column as i32).abs_diff(x) < 2;
+        self.picture.push(if lit { '#' } else { '.' });
+        self.pixels += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{examples, snapshot};
+
+    #[test]
+    fn small_example() {
+        let input = "noop
+addx 3
+addx -5";
+
+        let cycles: Vec<i32> = get_cycles(&parse_instructions(input).unwrap()).collect();
+        assert_eq!(cycles, [1, 1, 1, 4, 4, -1]);
+    }
+
+    #[test]
+    fn cycle_values() {
+        let cycles: Vec<_> =
+            get_cycles(&parse_instructions(examples::input("day10")).unwrap()).collect();
+        assert_eq!(cycles[19], 21);
+        assert_eq!(cycles[59], 19);
+        assert_eq!(cycles[99], 18);
+        assert_eq!(cycles[139], 21);
+        assert_eq!(cycles[179], 16);
+        assert_eq!(cycles[219], 18);
+    }
+
+    #[test]
+    fn example_part1() {
+        let strengths: Vec<_> =
+            signal_strengths(&parse_instructions(examples::input("day10")).unwrap()).collect();
+        assert_eq!(strengths[19], 420);
+        assert_eq!(strengths[59], 1140);
+        assert_eq!(strengths[99], 1800);
+        assert_eq!(strengths[139], 2940);
+        assert_eq!(strengths[179], 2880);
+        assert_eq!(strengths[219], 3960);
+    }
+
+    #[test]
+    fn snapshots() {
+        for (name, input) in snapshot::inputs(10) {
+            let program = parse_instructions(input).unwrap();
+            snapshot::assert_snapshot(&format!("{name}_crt"), &to_crt_string(&program, 40));
+        }
+    }
+
+    #[test]
+    fn short_programs() {
+        let day = crate::get_day(10).unwrap();
+        let context = Context::with_defaults(day);
+        // Too short for any interesting cycle, and long enough for the first
+        for (input, strength) in [("noop", 0), ("addx 1\n".repeat(15).as_str(), 200)] {
+            let program = parse_instructions(input).unwrap();
+            assert_eq!(interesting_signal_strengths(&program), strength);
+            let streamed = day
+                .stream(&mut input.as_bytes(), &[Part::One], &context)
+                .unwrap()
+                .unwrap();
+            assert_eq!(streamed, [Ok(Answer::Number(strength.into()))]);
+        }
+    }
+}
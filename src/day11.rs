@@ -0,0 +1,381 @@
+use std::collections::HashMap;
+use std::iter::repeat;
+
+use crate::cancel::Cancellation;
+use crate::json::Json;
+use crate::overflow::{self, Overflow, Unsigned};
+use crate::params::Param;
+use crate::parse::{self, ParseError};
+use crate::progress;
+use crate::trace::{self, Event};
+use crate::{Answer, Context, Solution};
+
+pub const INPUT: &str = include_str!("../inputs/day11.txt");
+
+pub struct Day11;
+
+/// A worry level, or a number it's tested or changed by
+type Level = Unsigned;
+
+impl Solution for Day11 {
+    type Parsed = Vec<Monkey>;
+
+    const PARAMS: &'static [Param] = &[
+        Param::new("part1_rounds", 20, "rounds of keep away in part 1"),
+        Param::new("part2_rounds", 10000, "rounds of keep away in part 2"),
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_monkeys(input)
+    }
+
+    fn part1(monkeys: &Self::Parsed, context: &Context) -> Result<Answer, String> {
+        Ok(monkey_business(
+            monkeys,
+            context.param("part1_rounds"),
+            true,
+            context.cancellation(),
+        )
+        .into())
+    }
+
+    fn part2(monkeys: &Self::Parsed, context: &Context) -> Option<Result<Answer, String>> {
+        Some(Ok(monkey_business(
+            monkeys,
+            context.param("part2_rounds"),
+            false,
+            context.cancellation(),
+        )
+        .into()))
+    }
+}
+
+#[derive(Clone)]
+pub struct Monkey {
+    items: Vec<Worry>,
+    items_inspected: usize,
+    /// How worry level changes after inspection
+    operation: Operation,
+    /// Takes in the worry level, determines which monkey to throw to
+    test_divisibility: Level,
+    test_true: usize,
+    test_false: usize,
+}
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Add(Level),
+    Multiply(Level),
+    Square,
+}
+
+impl Operation {
+    fn apply(self, worry: &mut Worry) {
+        match self {
+            Operation::Add(num) => worry.add(num),
+            Operation::Multiply(num) => worry.mul(num),
+            Operation::Square => worry.square(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Worry {
+    /// For small numbers (part 1), we can just use the number itself, until
+    /// it gets too big for it
+    number: Result<Level, Overflow>,
+    /// For larger numbers (part 2), we only store the remainders
+    /// when the number is divided by the divisor (which is the key)
+    remainders: HashMap<Level, Level>,
+}
+
+impl Worry {
+    fn add(&mut self, rhs: Level) {
+        self.number = self.number.and_then(|num| overflow::try_add(num, rhs));
+        for (divisor, remainder) in self.remainders.iter_mut() {
+            *remainder = overflow::add(*remainder, rhs) % *divisor;
+        }
+    }
+
+    fn mul(&mut self, rhs: Level) {
+        self.number = self.number.and_then(|num| overflow::try_mul(num, rhs));
+        for (divisor, remainder) in self.remainders.iter_mut() {
+            *remainder = overflow::mul(*remainder, rhs) % *divisor;
+        }
+    }
+
+    fn square(&mut self) {
+        self.number = self.number.and_then(|num| overflow::try_mul(num, num));
+        for (divisor, remainder) in self.remainders.iter_mut() {
+            *remainder = overflow::mul(*remainder, *remainder) % *divisor;
+        }
+    }
+
+    /// Only works while the number is being kept track of, so an overflow
+    /// before it is reported here
+    fn div(&mut self, rhs: Level) {
+        match &mut self.number {
+            Ok(num) => *num /= rhs,
+            Err(overflow) => overflow::report(*overflow),
+        }
+    }
+
+    fn divisible_by(&self, num: Level) -> bool {
+        if let Ok(number) = self.number {
+            number % num == 0
+        } else {
+            self.remainders[&num] == 0
+        }
+    }
+}
+
+fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let mut lines = parse::lines(input);
+    let mut monkeys = Vec::new();
+    let mut starting_items = Vec::new();
+    // Where each throw target was written, for reporting monkeys that don't exist
+    let mut targets = Vec::new();
+
+    while let Some(mut line) = lines.next() {
+        line.expect("Monkey ")?;
+        let number_line = line;
+        if line.number::<usize>()? != monkeys.len() {
+            return Err(number_line.error(format!("monkey {}", monkeys.len())));
+        }
+        line.expect(":")?;
+        line.finish()?;
+
+        let mut line = lines.expect_line("`  Starting items:`")?;
+        line.expect("  Starting items:")?;
+        let mut items = Vec::new();
+        // A monkey without items has nothing after the colon, not even the
+        // space, once the input has been normalized
+        if !line.rest().is_empty() {
+            line.expect(" ")?;
+            loop {
+                items.push(line.number::<Level>()?);
+                if !line.eat(", ") {
+                    break;
+                }
+            }
+        }
+        line.finish()?;
+        starting_items.push(items);
+
+        let mut line = lines.expect_line("`  Operation: new = old `")?;
+        line.expect("  Operation: new = old ")?;
+        let multiply = line.one_of(&[('*', true), ('+', false)])?;
+        line.expect(" ")?;
+        let operation = if multiply && line.eat("old") {
+            Operation::Square
+        } else if multiply {
+            Operation::Multiply(line.number()?)
+        } else {
+            Operation::Add(line.number()?)
+        };
+        line.finish()?;
+
+        let mut line = lines.expect_line("`  Test: divisible by `")?;
+        line.expect("  Test: divisible by ")?;
+        let divisor_line = line;
+        let test_divisibility: Level = line.number()?;
+        if test_divisibility == 0 {
+            return Err(divisor_line.error("a divisor greater than 0"));
+        }
+        line.finish()?;
+
+        let mut line = lines.expect_line("`    If true: throw to monkey `")?;
+        line.expect("    If true: throw to monkey ")?;
+        targets.push(line);
+        let test_true: usize = line.number()?;
+        line.finish()?;
+
+        let mut line = lines.expect_line("`    If false: throw to monkey `")?;
+        line.expect("    If false: throw to monkey ")?;
+        targets.push(line);
+        let test_false: usize = line.number()?;
+        line.finish()?;
+
+        monkeys.push(Monkey {
+            items: Vec::new(),
+            items_inspected: 0,
+            operation,
+            test_divisibility,
+            test_true,
+            test_false,
+        });
+
+        match lines.next() {
+            Some(line) if !line.is_empty() => {
+                return Err(line.error("an empty line between monkeys"));
+            }
+            _ => {}
+        }
+    }
+
+    if monkeys.len() < 2 {
+        return Err(lines.end_of_input("`Monkey ` (there must be at least two)"));
+    }
+    for target in targets {
+        let mut number = target;
+        if number.number::<usize>()? >= monkeys.len() {
+            return Err(target.error(format!("a monkey from 0 to {}", monkeys.len() - 1)));
+        }
+    }
+
+    // Now that we know all the numbers we'll use for divisibility tests,
+    // we can set up the worry levels
+    let worry = Worry {
+        number: Ok(0),
+        remainders: monkeys
+            .iter()
+            .map(|monkey| monkey.test_divisibility)
+            .zip(repeat(0))
+            .collect(),
+    };
+    for (monkey, items) in monkeys.iter_mut().zip(starting_items) {
+        monkey.items = items
+            .into_iter()
+            .map(|starting_worry| {
+                let mut w = worry.clone();
+                w.add(starting_worry);
+                w
+            })
+            .collect();
+    }
+
+    Ok(monkeys)
+}
+
+/// A monkey throwing an item to another after inspecting it
+struct Throw {
+    from: usize,
+    to: usize,
+    /// The item's worry level, if it's still being kept track of
+    worry: Option<Level>,
+}
+
+impl Event for Throw {
+    fn name(&self) -> &'static str {
+        "throw"
+    }
+
+    fn fields(&self, object: Json) -> Json {
+        object
+            .with("from", self.from)
+            .with("to", self.to)
+            .with("worry", self.worry)
+    }
+}
+
+/// The end of a round, with how many items each monkey has inspected so far
+struct Round {
+    round: usize,
+    inspected: Vec<usize>,
+}
+
+impl Event for Round {
+    fn name(&self) -> &'static str {
+        "round"
+    }
+
+    fn fields(&self, object: Json) -> Json {
+        object
+            .with("round", self.round)
+            .with("inspected", self.inspected.clone())
+    }
+}
+
+pub fn monkey_business(
+    monkeys: &[Monkey],
+    rounds: usize,
+    relief_after_inspect: bool,
+    cancellation: &Cancellation,
+) -> u64 {
+    let mut monkeys = monkeys.to_vec();
+
+    for round in 1..=rounds {
+        cancellation.check();
+        calculate_round(&mut monkeys, relief_after_inspect);
+        trace::emit(|| Round {
+            round,
+            inspected: monkeys.iter().map(|m| m.items_inspected).collect(),
+        });
+        progress::report(round, Some(rounds), "rounds");
+    }
+
+    monkeys.sort_by_key(|monkey| monkey.items_inspected);
+    monkeys
+        .iter()
+        .map(|m| m.items_inspected as u64)
+        .skip(monkeys.len() - 2)
+        .product()
+}
+
+fn calculate_round(monkeys: &mut [Monkey], relief_after_inspect: bool) {
+    for m in 0..monkeys.len() {
+        for mut worry in monkeys[m].items.split_off(0) {
+            // Getting worried while they inspect the item
+            monkeys[m].operation.apply(&mut worry);
+            if relief_after_inspect {
+                worry.div(3);
+            }
+            monkeys[m].items_inspected += 1;
+            let next_monkey = if worry.divisible_by(monkeys[m].test_divisibility) {
+                monkeys[m].test_true
+            } else {
+                monkeys[m].test_false
+            };
+            trace::emit(|| Throw {
+                from: m,
+                to: next_monkey,
+                worry: worry.number.ok(),
+            });
+            monkeys[next_monkey].items.push(worry);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn twenty_rounds_without_relief() {
+        assert_eq!(
+            monkey_business(
+                &parse_monkeys(examples::input("day11")).unwrap(),
+                20,
+                false,
+                &Cancellation::never()
+            ),
+            99 * 103
+        );
+    }
+
+    #[test]
+    fn worry_too_big_to_keep() {
+        let mut worry = Worry {
+            number: Ok(Level::MAX / 2),
+            remainders: HashMap::from([(7, 4)]),
+        };
+        worry.mul(3);
+        // Part 2 gets by with the remainders
+        assert!(worry.divisible_by(7) == (4 * 3 % 7 == 0));
+        // Part 1 needs the number itself
+        let overflow = overflow::checked(|| worry.div(3)).unwrap_err();
+        assert_eq!(overflow.location.file(), file!());
+        assert_eq!(overflow.expression(), format!("{} * 3", Level::MAX / 2));
+    }
+
+    #[test]
+    fn throw_to_missing_monkey() {
+        let input = examples::input("day11").replace("throw to monkey 3", "throw to monkey 7");
+        let error = parse_monkeys(&input).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 6, column 31: expected a monkey from 0 to 3, found `7`"
+        );
+    }
+}
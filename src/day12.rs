@@ -0,0 +1,292 @@
+use std::collections::VecDeque;
+
+use crate::cancel::{self, Cancellation};
+use crate::explore::{self, Explore, Query};
+use crate::grid::{Coord, Grid};
+use crate::json::Json;
+use crate::parse::ParseError;
+use crate::trace::{self, Event};
+use crate::{Answer, Context, Solution};
+
+pub const INPUT: &str = include_str!("../inputs/day12.txt");
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed = Heightmap;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_heightmap(input)
+    }
+
+    fn part1(heightmap: &Self::Parsed, context: &Context) -> Result<Answer, String> {
+        shortest_path(heightmap, false, context.cancellation()).map(Answer::from)
+    }
+
+    fn part2(heightmap: &Self::Parsed, context: &Context) -> Option<Result<Answer, String>> {
+        Some(shortest_path(heightmap, true, context.cancellation()).map(Answer::from))
+    }
+}
+
+impl Explore for Day12 {
+    /// The fewest steps to the end from each square, once a `steps` query
+    /// has searched for them
+    type Session = Option<Grid<Option<usize>>>;
+
+    const QUERIES: &'static [Query] = &[
+        Query::new(
+            "steps",
+            &["X", "Y"],
+            "the fewest steps from a square to `E`",
+        ),
+        Query::new("height", &["X", "Y"], "the height of a square"),
+        Query::new("start", &[], "where `S` is"),
+        Query::new("end", &[], "where `E` is"),
+    ];
+
+    fn query(
+        heightmap: &Self::Parsed,
+        steps_to_end: &mut Self::Session,
+        name: &str,
+        args: &[&str],
+    ) -> Result<String, String> {
+        let square = || -> Result<Coord, String> {
+            let square = Coord::new(explore::arg(args, 0, "X")?, explore::arg(args, 1, "Y")?);
+            match heightmap.heights.contains(square) {
+                true => Ok(square),
+                false => Err(format!("{square} isn't on the map")),
+            }
+        };
+        match name {
+            "steps" => {
+                let square = square()?;
+                let steps_to_end = steps_to_end.get_or_insert_with(|| {
+                    let mut map = Map::new(heightmap, false);
+                    map.search(&cancel::NEVER);
+                    map.steps_to_destination
+                });
+                Ok(match steps_to_end[square] {
+                    Some(steps) => steps.to_string(),
+                    None => format!("`E` can't be reached from {square}"),
+                })
+            }
+            "height" => Ok(char::from(b'a' + heightmap.heights[square()?]).to_string()),
+            "start" => Ok(heightmap.start.to_string()),
+            _ => Ok(heightmap.end.to_string()),
+        }
+    }
+}
+
+pub struct Heightmap {
+    /// Heights from 0 (`a`) to 25 (`z`), with the start and end already
+    /// replaced by their heights
+    heights: Grid<u8>,
+    start: Coord,
+    end: Coord,
+}
+
+fn parse_heightmap(input: &str) -> Result<Heightmap, ParseError> {
+    let mut start = None;
+    let mut end = None;
+    let heights = Grid::parse(
+        input,
+        "a height from `a` to `z`, or one `S` and `E`",
+        |position, c| match c {
+            'S' if start.is_none() => {
+                start = Some(position);
+                Some(0)
+            }
+            'E' if end.is_none() => {
+                end = Some(position);
+                Some(b'z' - b'a')
+            }
+            'a'..='z' => Some(c as u8 - b'a'),
+            _ => None,
+        },
+    )?;
+
+    // Every line is a row, so anything missing would be after the last one
+    let after_last_row = heights.height() + 1;
+    match (start, end) {
+        (Some(start), Some(end)) => Ok(Heightmap {
+            heights,
+            start,
+            end,
+        }),
+        (None, _) => Err(ParseError::end_of_input(
+            after_last_row,
+            "a starting position `S`",
+        )),
+        (_, None) => Err(ParseError::end_of_input(
+            after_last_row,
+            "a best signal position `E`",
+        )),
+    }
+}
+
+/// The search reaching points from a point one step closer to the end
+struct Expand {
+    steps: usize,
+    points: Vec<Coord>,
+}
+
+impl Event for Expand {
+    fn name(&self) -> &'static str {
+        "expand"
+    }
+
+    fn fields(&self, object: Json) -> Json {
+        object
+            .with("steps", self.steps)
+            .with("points", self.points.clone())
+    }
+}
+
+struct Map<'a> {
+    heights: &'a Grid<u8>,
+    steps_to_destination: Grid<Option<usize>>,
+    starting_points: Vec<Coord>,
+    end: Coord,
+}
+
+impl<'a> Map<'a> {
+    fn new(heightmap: &'a Heightmap, start_at_any_a: bool) -> Self {
+        let heights = &heightmap.heights;
+        let end = heightmap.end;
+        let starting_points = if start_at_any_a {
+            heights
+                .iter()
+                .filter(|(_, &height)| height == 0)
+                .map(|(position, _)| position)
+                .collect()
+        } else {
+            vec![heightmap.start]
+        };
+
+        let mut steps_to_destination = Grid::new(heights.width(), heights.height(), None);
+        steps_to_destination[end] = Some(0);
+        Self {
+            heights,
+            starting_points,
+            end,
+            steps_to_destination,
+        }
+    }
+
+    fn get_height(&self, point: Coord) -> u8 {
+        self.heights[point]
+    }
+
+    fn set_steps_to_destination(&mut self, point: Coord, steps_to_destination: usize) -> bool {
+        // Check if the previous point to this path was better
+        if let Some(previous) = self.steps_to_destination[point] {
+            if previous < steps_to_destination {
+                return false;
+            }
+        }
+
+        self.steps_to_destination[point] = Some(steps_to_destination);
+        true
+    }
+
+    fn get_steps_to_destination(&self, point: Coord) -> Option<usize> {
+        self.steps_to_destination[point]
+    }
+
+    /// Finds the fewest steps to the end from every point that can reach it
+    fn search(&mut self, cancellation: &Cancellation) {
+        // Start exploring from the end
+        let mut points_to_explore =
+            VecDeque::from([(self.get_adjacent_points_to_explore(self.end), 1)]);
+
+        while let Some((points, steps_to_destination)) = points_to_explore.pop_front() {
+            cancellation.check();
+            trace::emit(|| Expand {
+                steps: steps_to_destination,
+                points: points.clone(),
+            });
+            for point in points {
+                if self.get_steps_to_destination(point).is_some() {
+                    continue;
+                }
+                if self.set_steps_to_destination(point, steps_to_destination) {
+                    let points = self.get_adjacent_points_to_explore(point);
+                    if !points.is_empty() {
+                        points_to_explore.push_back((points, steps_to_destination + 1));
+                    }
+                }
+            }
+        }
+    }
+
+    fn get_adjacent_points_to_explore(&self, point: Coord) -> Vec<Coord> {
+        self.heights
+            .neighbours4(point)
+            .filter(|&p| {
+                self.get_height(p) + 1 >= self.get_height(point)
+                    && self.get_steps_to_destination(p).is_none()
+            })
+            .collect()
+    }
+}
+
+pub fn shortest_path(
+    heightmap: &Heightmap,
+    start_at_any_a: bool,
+    cancellation: &Cancellation,
+) -> Result<usize, String> {
+    let mut map = Map::new(heightmap, start_at_any_a);
+    map.search(cancellation);
+
+    // Find the starting point that had the shortest path to the end
+    map.starting_points
+        .iter()
+        .filter_map(|point| map.get_steps_to_destination(*point))
+        .min()
+        .ok_or_else(|| match start_at_any_a {
+            true => "`E` can't be reached from any square of height `a`".to_string(),
+            false => "`E` can't be reached from `S`".to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn unreachable_end() {
+        let heightmap = parse_heightmap("SzE").unwrap();
+        assert_eq!(
+            shortest_path(&heightmap, false, &cancel::NEVER),
+            Err("`E` can't be reached from `S`".to_string())
+        );
+        assert_eq!(
+            shortest_path(&heightmap, true, &cancel::NEVER),
+            Err("`E` can't be reached from any square of height `a`".to_string())
+        );
+    }
+
+    #[test]
+    fn querying_squares() {
+        let heightmap = parse_heightmap(examples::input("day12")).unwrap();
+        let mut steps_to_end = None;
+        let mut query =
+            |name, args: &[&str]| Day12::query(&heightmap, &mut steps_to_end, name, args);
+        assert_eq!(query("steps", &["0", "0"]).unwrap(), "31");
+        assert_eq!(query("steps", &["5", "2"]).unwrap(), "0");
+        assert_eq!(query("height", &["5", "2"]).unwrap(), "z");
+        assert_eq!(query("start", &[]).unwrap(), "(0, 0)");
+        assert_eq!(query("end", &[]).unwrap(), "(5, 2)");
+        assert_eq!(
+            query("steps", &["8", "0"]).unwrap_err(),
+            "(8, 0) isn't on the map"
+        );
+        assert_eq!(
+            query("height", &["x", "0"]).unwrap_err(),
+            "X must be a number, not `x`"
+        );
+        // Kept for the next `steps` query
+        assert_eq!(steps_to_end.unwrap()[Coord::new(0, 0)], Some(31));
+    }
+}
@@ -1,27 +1,76 @@
 use std::cmp::Ordering;
 
+use crate::parse::{self, Line, ParseError};
+use crate::{Answer, Context, Solution};
+
 const DIVIDER_PACKETS: [&str; 2] = ["[[2]]", "[[6]]"];
 
-fn main() {
-    let input = include_str!("day13.txt");
-    println!(
-        "Part 1: {:?}",
-        packets_in_right_order(input).iter().sum::<usize>()
-    );
-    println!("Part 2: {}", divider_packets_indices_product(input));
+pub const INPUT: &str = include_str!("../inputs/day13.txt");
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    /// Every packet, in the order they appear
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse::lines(input)
+            .filter(|line| !line.is_empty())
+            .map(|mut line| {
+                validate_list(&mut line)?;
+                line.finish()?;
+                Ok(line.text().to_string())
+            })
+            .collect()
+    }
+
+    fn part1(packets: &Self::Parsed, _context: &Context) -> Result<Answer, String> {
+        Ok(packets_in_right_order(packets).iter().sum::<usize>().into())
+    }
+
+    fn part2(packets: &Self::Parsed, _context: &Context) -> Option<Result<Answer, String>> {
+        Some(Ok(divider_packets_indices_product(packets).into()))
+    }
+}
+
+/// Checks that the line holds a well-formed list, since `compare_packets`
+/// works directly on the characters
+fn validate_list(line: &mut Line) -> Result<(), ParseError> {
+    line.expect("[")?;
+    if line.eat("]") {
+        return Ok(());
+    }
+    loop {
+        if line.peek_char() == Some('[') {
+            validate_list(line)?;
+        } else {
+            let column = line.column();
+            let number = line.number::<u8>()?;
+            if number > 10 {
+                return Err(line.error_at(column, number.to_string(), "a number from 0 to 10"));
+            }
+        }
+        if line.eat("]") {
+            return Ok(());
+        }
+        if !line.eat(",") {
+            return Err(line.error("`,` or `]`"));
+        }
+    }
 }
 
-fn packets_in_right_order<'a>(input: &'a str) -> Vec<usize> {
+pub fn packets_in_right_order(packets: &[String]) -> Vec<usize> {
     let mut packets_in_order = Vec::new();
 
     let mut first = None;
-    for (index, line) in input.lines().filter(|line| !line.is_empty()).enumerate() {
+    for (index, line) in packets.iter().enumerate() {
         if let Some(first) = first.take() {
+            let pair = index / 2 + 1;
             if compare_packets(first, line) {
-                println!("Right order");
-                packets_in_order.push(index / 2 + 1);
+                crate::info!("pair {pair} is in the right order");
+                packets_in_order.push(pair);
             } else {
-                println!("Wrong order");
+                crate::info!("pair {pair} is in the wrong order");
             }
         } else {
             first = Some(line);
@@ -32,11 +81,11 @@ fn packets_in_right_order<'a>(input: &'a str) -> Vec<usize> {
     packets_in_order
 }
 
-fn divider_packets_indices_product(input: &str) -> usize {
-    let mut lines: Vec<&str> = input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .chain(DIVIDER_PACKETS.into_iter())
+pub fn divider_packets_indices_product(packets: &[String]) -> usize {
+    let mut lines: Vec<&str> = packets
+        .iter()
+        .map(String::as_str)
+        .chain(DIVIDER_PACKETS)
         .collect();
     // We need to use unstable sorting because we're not checking if packets are "equal"
     lines.sort_unstable_by(|a, b| {
@@ -46,9 +95,7 @@ fn divider_packets_indices_product(input: &str) -> usize {
             Ordering::Greater
         }
     });
-    for line in lines.iter() {
-        println!("{}", line);
-    }
+    crate::debug!("sorted packets:\n{}", lines.join("\n"));
     let mut lines = lines.into_iter();
     // Find the divider packets (with 1-based indexing)
     let first_divider = 1 + lines.position(|line| line == DIVIDER_PACKETS[0]).unwrap();
@@ -58,8 +105,7 @@ fn divider_packets_indices_product(input: &str) -> usize {
 }
 
 fn compare_packets(left: &str, right: &str) -> bool {
-    dbg!(&left);
-    dbg!(&right);
+    crate::trace!("comparing {left} with {right}");
     let mut left = left.chars().peekable();
     let mut right = right.chars().peekable();
 
@@ -75,7 +121,7 @@ fn compare_packets(left: &str, right: &str) -> bool {
         let b = right_put_back.pop().or_else(|| right.next());
         let a_next = left_put_back.last().or(left.peek()).copied();
         let b_next = right_put_back.last().or(right.peek()).copied();
-        println!("{a:?} {b:?}");
+        crate::trace!("{a:?} {b:?}");
         match (a, b) {
             // If both are empty, they're in the right order
             (None, None) => return true,
@@ -113,19 +159,21 @@ fn compare_packets(left: &str, right: &str) -> bool {
             // If one is a number but the other is a list,
             // turn the number into a list of one element and compare them
             (Some(a), Some('[')) if a.is_numeric() => {
+                // The 0 of a 10 might have been put back already
+                let zero = (a_next == Some('0'))
+                    .then(|| left_put_back.pop().or_else(|| left.next()).unwrap());
                 left_put_back.push(']');
-                if a_next == Some('0') {
-                    left_put_back.push(left.next().unwrap());
-                }
+                left_put_back.extend(zero);
                 left_put_back.push(a);
                 left_put_back.push('[');
                 right_put_back.push('[');
             }
             (Some('['), Some(b)) if b.is_numeric() => {
+                // The 0 of a 10 might have been put back already
+                let zero = (b_next == Some('0'))
+                    .then(|| right_put_back.pop().or_else(|| right.next()).unwrap());
                 right_put_back.push(']');
-                if b_next == Some('0') {
-                    right_put_back.push(right.next().unwrap());
-                }
+                right_put_back.extend(zero);
                 right_put_back.push(b);
                 right_put_back.push('[');
                 left_put_back.push('[');
@@ -140,38 +188,21 @@ fn compare_packets(left: &str, right: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    static EXAMPLE: &str = "[1,1,3,1,1]
-[1,1,5,1,1]
-
-[[1],[2,3,4]]
-[[1],4]
-
-[9]
-[[8,7,6]]
-
-[[4,4],4,4]
-[[4,4],4,4,4]
-
-[7,7,7,7]
-[7,7,7]
-
-[]
-[3]
-
-[[[]]]
-[[]]
-
-[1,[2,[3,[4,[5,6,7]]]],8,9]
-[1,[2,[3,[4,[5,6,0]]]],8,9]";
+    use crate::examples;
 
     #[test]
     fn example_part1() {
-        assert_eq!(packets_in_right_order(EXAMPLE), &[1, 2, 4, 6]);
+        assert_eq!(
+            packets_in_right_order(&Day13::parse(examples::input("day13")).unwrap()),
+            &[1, 2, 4, 6]
+        );
     }
 
     #[test]
-    fn example_part2() {
-        assert_eq!(divider_packets_indices_product(EXAMPLE), 140);
+    fn tens_wrapped_in_lists_twice() {
+        // Comparing 10 with [[7],3] turns it into [10] and then [[10]]
+        assert!(compare_packets("[[[7],3]]", "[10]"));
+        assert!(!compare_packets("[10]", "[[[7],3]]"));
+        assert!(compare_packets("[[[10]]]", "[10,1]"));
     }
 }
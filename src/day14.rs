@@ -0,0 +1,277 @@
+use std::fmt;
+
+use crate::cancel::Cancellation;
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::json::Json;
+use crate::log::Level;
+use crate::parse::{self, ParseError};
+use crate::progress;
+use crate::trace::{self, Event};
+use crate::{Answer, Context, Solution};
+
+pub const INPUT: &str = include_str!("../inputs/day14.txt");
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    /// The cave with only the rocks filled in
+    type Parsed = Map;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Map::new(input)
+    }
+
+    fn part1(map: &Self::Parsed, context: &Context) -> Result<Answer, String> {
+        Ok(count_sand(map, context.cancellation()).into())
+    }
+
+    fn part2(_map: &Self::Parsed, _context: &Context) -> Option<Result<Answer, String>> {
+        None
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Contents {
+    Air,
+    Rock,
+    Sand,
+}
+
+impl Contents {
+    fn to_char(self) -> char {
+        match self {
+            Contents::Air => '.',
+            Contents::Rock => '#',
+            Contents::Sand => 'o',
+        }
+    }
+}
+
+/// The cave, with columns numbered as in the input
+#[derive(Clone)]
+pub struct Map {
+    cave: Grid<Contents>,
+}
+
+impl Map {
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let paths = parse_paths(input)?;
+
+        // Find the maximum coordinates to determine the size of the map
+        let mut min_col = usize::MAX;
+        let mut max_col = 0;
+        let mut max_row = 0;
+        for &(col, row) in paths.iter().flatten() {
+            min_col = min_col.min(col);
+            max_col = max_col.max(col);
+            max_row = max_row.max(row);
+        }
+        let width = max_col - min_col + 1;
+
+        // Populate the map with rocks
+        let mut cave =
+            Grid::new(width, max_row + 1, Contents::Air).with_origin(Point::new(min_col as i64, 0));
+
+        for path in &paths {
+            let mut coordinates = path.iter().copied();
+            let (mut from_col, mut from_row) = coordinates.next().unwrap();
+            for (to_col, to_row) in coordinates {
+                for col in from_col.min(to_col)..=to_col.max(from_col) {
+                    for row in from_row.min(to_row)..=from_row.max(to_row) {
+                        cave.set(Point::new(col as i64, row as i64), Contents::Rock);
+                    }
+                }
+                from_col = to_col;
+                from_row = to_row;
+            }
+        }
+        Ok(Self { cave })
+    }
+
+    fn set_point(&mut self, row: i64, col: i64, value: Contents) {
+        self.cave[Point::new(col, row)] = value;
+    }
+
+    fn get_point(&self, row: i64, col: i64) -> Option<Contents> {
+        self.cave.get(Point::new(col, row)).copied()
+    }
+}
+
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.cave.render(|point| point.to_char()))
+    }
+}
+
+/// The most squares the cave can have, so a few far-off points can't make
+/// it too big to fit in memory
+const MAX_CAVE_SIZE: usize = 1 << 24;
+
+/// Parses each line into the (col, row) points along a path of rock
+fn parse_paths(input: &str) -> Result<Vec<Vec<(usize, usize)>>, ParseError> {
+    let mut lines = parse::lines(input);
+    // The columns and rows the cave spans so far
+    let (mut min_col, mut max_col, mut max_row) = (usize::MAX, 0, 0);
+    let paths: Vec<_> = lines
+        .by_ref()
+        .filter(|line| !line.is_empty())
+        .map(|mut line| {
+            let mut path: Vec<(usize, usize)> = Vec::new();
+            loop {
+                let point_line = line;
+                let col = line.number()?;
+                line.expect(",")?;
+                let row = line.number()?;
+                if let Some(&(from_col, from_row)) = path.last() {
+                    if from_col != col && from_row != row {
+                        return Err(point_line.error_at(
+                            point_line.column(),
+                            format!("{col},{row}"),
+                            "a point in a straight line from the previous one",
+                        ));
+                    }
+                }
+                (min_col, max_col, max_row) =
+                    (min_col.min(col), max_col.max(col), max_row.max(row));
+                let size = (max_col - min_col)
+                    .checked_add(1)
+                    .and_then(|width| width.checked_mul(max_row.checked_add(1)?));
+                if size.is_none_or(|size| size > MAX_CAVE_SIZE) {
+                    return Err(point_line.error_at(
+                        point_line.column(),
+                        format!("{col},{row}"),
+                        format!("a point keeping the cave within {MAX_CAVE_SIZE} squares"),
+                    ));
+                }
+                path.push((col, row));
+                if !line.eat(" -> ") {
+                    break;
+                }
+            }
+            line.finish()?;
+            Ok(path)
+        })
+        .collect::<Result<_, ParseError>>()?;
+
+    if paths.is_empty() {
+        return Err(lines.end_of_input("a path of rock"));
+    }
+    Ok(paths)
+}
+
+/// A grain of sand coming to rest
+struct Rest {
+    /// Counting from 1
+    grain: usize,
+    position: Point<i64>,
+}
+
+impl Event for Rest {
+    fn name(&self) -> &'static str {
+        "rest"
+    }
+
+    fn fields(&self, object: Json) -> Json {
+        object
+            .with("grain", self.grain)
+            .with("position", self.position)
+    }
+}
+
+/// Drops a grain of sand from the source, returning where it comes to
+/// rest, or `None` if it falls off the map
+fn add_sand(map: &mut Map) -> Option<Point<i64>> {
+    let mut row = 0;
+    let mut col = 500;
+
+    loop {
+        let below = map.get_point(row + 1, col);
+        let diagonal_left = map.get_point(row + 1, col - 1);
+        let diagonal_right = map.get_point(row + 1, col + 1);
+
+        if below == Some(Contents::Air) {
+            row += 1;
+        } else if diagonal_left == Some(Contents::Air) {
+            row += 1;
+            col -= 1;
+        } else if diagonal_right == Some(Contents::Air) {
+            row += 1;
+            col += 1;
+        } else if below.is_none() || diagonal_left.is_none() || diagonal_right.is_none() {
+            // Fell off, can't put any more sand
+            crate::debug!("sand fell off the map at ({col}, {row})");
+            return None;
+        } else {
+            break;
+        }
+    }
+    map.set_point(row, col, Contents::Sand);
+    Some(Point::new(col, row))
+}
+
+pub fn count_sand(map: &Map, cancellation: &Cancellation) -> usize {
+    pour_sand(map, cancellation).1
+}
+
+/// Drops sand into the cave until it falls off the map, returning the cave
+/// with the sand that came to rest and how many grains did
+fn pour_sand(map: &Map, cancellation: &Cancellation) -> (Map, usize) {
+    let mut map = map.clone();
+    let mut count = 0;
+    while let Some(position) = add_sand(&mut map) {
+        cancellation.check();
+        count += 1;
+        trace::emit(|| Rest {
+            grain: count,
+            position,
+        });
+        progress::report(count, None, "grains");
+    }
+    if crate::log_enabled!(Level::Debug) {
+        let rows: Vec<_> = (map.to_string().lines().enumerate())
+            .map(|(index, line)| format!("{index:3} {line}"))
+            .collect();
+        crate::debug!("the cave with {count} units of sand:\n{}", rows.join("\n"));
+    }
+    (map, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cancel, examples, snapshot};
+
+    #[test]
+    fn snapshots() {
+        for (name, input) in snapshot::inputs(14) {
+            let map = Map::new(input).unwrap();
+            snapshot::assert_snapshot(&format!("{name}_cave"), &map.to_string());
+            let (filled, _) = pour_sand(&map, &cancel::NEVER);
+            snapshot::assert_snapshot(&format!("{name}_sand"), &filled.to_string());
+        }
+    }
+
+    #[test]
+    fn paths_going_up() {
+        // The example's paths drawn from their other ends
+        let reversed = "496,6 -> 498,6 -> 498,4
+494,9 -> 502,9 -> 502,4 -> 503,4";
+        assert_eq!(
+            Map::new(reversed).unwrap().to_string(),
+            Map::new(examples::input("day14")).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn caves_too_big() {
+        let error = Map::new("500,0 -> 500,100000000000").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 10: expected a point keeping the cave within 16777216 squares, \
+             found `500,100000000000`"
+        );
+        let error = Map::new("0,0 -> 3000000000,0\n0,1 -> 0,3").err().unwrap();
+        assert_eq!(error.line, 1);
+    }
+}
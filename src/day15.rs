@@ -0,0 +1,355 @@
+use crate::cancel::Cancellation;
+use crate::differential::Implementation;
+use crate::explore::{self, Explore, Query};
+use crate::geometry::{Bounds, Point, Vec2};
+use crate::overflow::{self, Signed, SIGNED_MAX};
+use crate::params::Param;
+use crate::parse::{self, ParseError};
+use crate::progress;
+use crate::{Answer, Context, Solution};
+
+pub const INPUT: &str = include_str!("../inputs/day15.txt");
+
+pub struct Day15;
+
+/// The largest `max_coord`, leaving room to step past it and for the tuning
+/// frequency to fit in an answer
+const MAX_COORD: i64 = {
+    let limit = i64::MAX / 4000001;
+    if SIGNED_MAX - 1 < limit {
+        SIGNED_MAX - 1
+    } else {
+        limit
+    }
+};
+
+impl Solution for Day15 {
+    type Parsed = Vec<Sensor>;
+
+    const PARAMS: &'static [Param] = &[
+        // Half the range, so the row's distance from a sensor always fits
+        Param::new("row", 2000000, "the row to check for part 1")
+            .at_least(-SIGNED_MAX / 2)
+            .at_most(SIGNED_MAX / 2),
+        Param::new(
+            "max_coord",
+            4000000,
+            "the largest x and y the distress beacon can have",
+        )
+        .at_most(MAX_COORD),
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(sensors: &Self::Parsed, context: &Context) -> Result<Answer, String> {
+        Ok(count_spots_without_sensors(sensors, context.param("row")).into())
+    }
+
+    fn part2(sensors: &Self::Parsed, context: &Context) -> Option<Result<Answer, String>> {
+        Some(
+            distress_signal_tuning_frequency(
+                sensors,
+                context.param("max_coord"),
+                context.cancellation(),
+            )
+            .map(Answer::from),
+        )
+    }
+}
+
+impl Explore for Day15 {
+    type Session = ();
+
+    const QUERIES: &'static [Query] = &[
+        Query::new(
+            "covers",
+            &["X", "Y"],
+            "the sensors that rule out a beacon at a point",
+        ),
+        Query::new("sensors", &[], "every sensor, with its nearest beacon"),
+        Query::new(
+            "row",
+            &["Y"],
+            "how many points in a row can't have a beacon",
+        ),
+    ];
+
+    fn query(
+        sensors: &Self::Parsed,
+        _session: &mut (),
+        name: &str,
+        args: &[&str],
+    ) -> Result<String, String> {
+        match name {
+            "covers" => {
+                let point = Point::new(explore::arg(args, 0, "X")?, explore::arg(args, 1, "Y")?);
+                let covering: Vec<_> = sensors
+                    .iter()
+                    .filter(|sensor| {
+                        sensor.location.manhattan_distance(point) <= sensor.distance_to_beacon
+                    })
+                    .map(Sensor::describe)
+                    .collect();
+                if covering.is_empty() {
+                    Ok(format!("no sensor covers {point}"))
+                } else {
+                    Ok(covering.join("\n"))
+                }
+            }
+            "sensors" => Ok(sensors
+                .iter()
+                .map(Sensor::describe)
+                .collect::<Vec<_>>()
+                .join("\n")),
+            _ => Ok(count_spots_without_sensors(sensors, explore::arg(args, 0, "Y")?).to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Sensor {
+    location: Point<Signed>,
+    nearest_beacon: Point<Signed>,
+    distance_to_beacon: Signed,
+}
+
+impl Sensor {
+    pub fn new(location: Point<Signed>, nearest_beacon: Point<Signed>) -> Self {
+        Sensor {
+            location,
+            nearest_beacon,
+            distance_to_beacon: location.manhattan_distance(nearest_beacon),
+        }
+    }
+
+    /// Like `sensor at (2, 18), beacon at (-2, 15), 7 away`
+    fn describe(&self) -> String {
+        format!(
+            "sensor at {}, beacon at {}, {} away",
+            self.location, self.nearest_beacon, self.distance_to_beacon
+        )
+    }
+}
+
+/// Every implementation of part 1, which are checked against each other in
+/// the tests
+pub const COUNT_SPOTS_WITHOUT_SENSORS: &[Implementation<(Vec<Sensor>, Signed), usize>] = &[
+    Implementation::new("naive", |(sensors, row)| {
+        spots_without_sensors(sensors, *row).len()
+    }),
+    Implementation::new("merged ranges", |(sensors, row)| {
+        count_spots_without_sensors(sensors, *row)
+    }),
+];
+
+fn parse_input(input: &str) -> Result<Vec<Sensor>, ParseError> {
+    parse::lines(input)
+        .map(|mut line| {
+            line.expect("Sensor at x=")?;
+            let sensor_x = line.number()?;
+            line.expect(", y=")?;
+            let sensor_y = line.number()?;
+            line.expect(": closest beacon is at x=")?;
+            let beacon_x = line.number()?;
+            line.expect(", y=")?;
+            let beacon_y = line.number()?;
+            line.finish()?;
+
+            Ok(Sensor::new(
+                Point::new(sensor_x, sensor_y),
+                Point::new(beacon_x, beacon_y),
+            ))
+        })
+        .collect()
+}
+
+pub fn spots_without_sensors(sensors: &[Sensor], row: Signed) -> Vec<Signed> {
+    // Every spot a sensor can rule out is within this range
+    let Some(covered) = Bounds::of(sensors.iter().flat_map(|sensor| {
+        let reach = Vec2::new(sensor.distance_to_beacon, 0);
+        [sensor.location - reach, sensor.location + reach]
+    })) else {
+        return Vec::new();
+    };
+
+    let mut spots_without_sensors = Vec::new();
+    let mut x = covered.min.x;
+    while x <= covered.max.x {
+        let point = Point::new(x, row);
+        let is_beacon = sensors.iter().any(|sensor| sensor.nearest_beacon == point);
+        let within_range = sensors
+            .iter()
+            .any(|sensor| sensor.location.manhattan_distance(point) <= sensor.distance_to_beacon);
+        if within_range && !is_beacon {
+            spots_without_sensors.push(x);
+        }
+        x += 1;
+    }
+    spots_without_sensors
+}
+
+/// Like [`spots_without_sensors`], but only counts them, by merging the
+/// range each sensor covers in the row instead of checking every spot
+pub fn count_spots_without_sensors(sensors: &[Sensor], row: Signed) -> usize {
+    let mut ranges: Vec<(Signed, Signed)> = sensors
+        .iter()
+        .filter_map(|sensor| {
+            let reach = overflow::sub(
+                sensor.distance_to_beacon,
+                overflow::cast(sensor.location.y.abs_diff(row)),
+            );
+            (reach >= 0).then(|| {
+                (
+                    overflow::sub(sensor.location.x, reach),
+                    overflow::add(sensor.location.x, reach),
+                )
+            })
+        })
+        .collect();
+    ranges.sort();
+
+    let mut covered = 0;
+    // The first spot that hasn't been counted yet
+    let mut next = Signed::MIN;
+    for (from, to) in ranges {
+        let from = from.max(next);
+        if from <= to {
+            covered += overflow::cast::<_, usize>(overflow::sub(to, from)) + 1;
+            next = overflow::add(to, 1);
+        }
+    }
+
+    let mut beacons: Vec<Signed> = sensors
+        .iter()
+        .map(|sensor| sensor.nearest_beacon)
+        .filter(|beacon| beacon.y == row)
+        .map(|beacon| beacon.x)
+        .collect();
+    beacons.sort();
+    beacons.dedup();
+    // Every beacon is within range of the sensor it's nearest to
+    covered - beacons.len()
+}
+
+pub fn distress_signal_tuning_frequency(
+    sensors: &[Sensor],
+    max_coord: usize,
+    cancellation: &Cancellation,
+) -> Result<u64, String> {
+    let max_x: Signed = overflow::cast(max_coord);
+    for row in 0..=max_coord {
+        cancellation.check();
+        progress::report(row, Some(max_coord + 1), "rows");
+        let y: Signed = overflow::cast(row);
+        let mut ranges = Vec::new();
+
+        // For each sensor, calculate the range of x values in this row that the
+        // sensor "covers"
+        for sensor in sensors {
+            let max_dist_from_x = overflow::sub(
+                sensor.distance_to_beacon,
+                overflow::cast(sensor.location.y.abs_diff(y)),
+            );
+            if max_dist_from_x < 0 {
+                continue;
+            }
+            let range = (
+                overflow::sub(sensor.location.x, max_dist_from_x).max(0),
+                overflow::add(sensor.location.x, max_dist_from_x).min(max_x),
+            );
+            // Sensors to the side of the search area can reach into the row
+            // without reaching the area
+            if range.0 <= range.1 {
+                ranges.push(range);
+            }
+        }
+
+        // Combine the ranges to find the spots in the row they don't cover
+        ranges.sort_unstable();
+        let mut uncovered = Vec::new();
+        // The first spot that isn't known to be covered
+        let mut next = 0;
+        for (from, to) in ranges {
+            if from > next {
+                uncovered.push((next, from - 1));
+            }
+            next = next.max(overflow::add(to, 1));
+        }
+        if next <= max_x {
+            uncovered.push((next, max_x));
+        }
+        match uncovered[..] {
+            [] => {}
+            [(x, to)] if x == to => {
+                let x: u64 = overflow::cast(x);
+                return Ok(overflow::add(
+                    overflow::mul(x, 4000000),
+                    overflow::cast(row),
+                ));
+            }
+            _ => {
+                return Err(format!(
+                    "more than one spot in row {row} could be the distress beacon"
+                ))
+            }
+        }
+    }
+    Err("every spot is within range of a sensor".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differential::{self, Shrink};
+    use crate::examples;
+    use crate::random::Rng;
+
+    impl Shrink for Sensor {
+        fn shrink(&self) -> Vec<Self> {
+            (self.location, self.nearest_beacon)
+                .shrink()
+                .into_iter()
+                .map(|(location, beacon)| Sensor::new(location, beacon))
+                .collect()
+        }
+    }
+
+    fn sensor(rng: &mut Rng) -> Sensor {
+        let mut coord = || rng.range(-20..=20) as Signed;
+        Sensor::new(Point::new(coord(), coord()), Point::new(coord(), coord()))
+    }
+
+    #[test]
+    fn implementations_agree() {
+        differential::assert_agree(COUNT_SPOTS_WITHOUT_SENSORS, |rng| {
+            let sensors = (0..rng.below(6)).map(|_| sensor(rng)).collect();
+            (sensors, rng.range(-20..=20) as Signed)
+        });
+    }
+
+    #[test]
+    fn querying_sensors() {
+        let sensors = parse_input(examples::input("day15")).unwrap();
+        let query = |name, args: &[&str]| Day15::query(&sensors, &mut (), name, args).unwrap();
+        assert_eq!(
+            query("covers", &["2", "12"]),
+            "sensor at (2, 18), beacon at (-2, 15), 7 away\n\
+             sensor at (0, 11), beacon at (2, 10), 3 away"
+        );
+        assert_eq!(query("covers", &["14", "11"]), "no sensor covers (14, 11)");
+        assert_eq!(query("row", &["10"]), "26");
+    }
+
+    #[test]
+    fn malformed_report() {
+        let error = parse_input("Sensor at x=2, y=18: closest beacon is at x=-2, y=")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 51: expected a number, found end of line"
+        );
+    }
+}
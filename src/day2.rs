@@ -0,0 +1,152 @@
+use std::io::BufRead;
+
+use crate::overflow;
+use crate::parse::{self, Line, ParseError};
+use crate::stream::{self, StreamError, Streaming};
+use crate::{Answer, Context, Part, Solution};
+
+pub const INPUT: &str = include_str!("../inputs/day2.txt");
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Parsed = Vec<Round>;
+
+    fn parse(games: &str) -> Result<Self::Parsed, ParseError> {
+        parse::lines(games).map(Round::parse).collect()
+    }
+
+    fn part1(rounds: &Self::Parsed, _context: &Context) -> Result<Answer, String> {
+        Ok(score_part1(rounds).into())
+    }
+
+    fn part2(rounds: &Self::Parsed, _context: &Context) -> Option<Result<Answer, String>> {
+        Some(Ok(score_part2(rounds).into()))
+    }
+}
+
+impl Streaming for Day2 {
+    fn stream(
+        input: &mut dyn BufRead,
+        parts: &[Part],
+        _context: &Context,
+    ) -> Result<Vec<Result<Answer, String>>, StreamError> {
+        let (mut part1, mut part2): (u64, u64) = (0, 0);
+        stream::for_each_line(input, |line| {
+            let round = Round::parse(line)?;
+            part1 = overflow::add(part1, round.score_part1().into());
+            part2 = overflow::add(part2, round.score_part2().into());
+            Ok(())
+        })?;
+        Ok(parts
+            .iter()
+            .map(|part| match part {
+                Part::One => Ok(part1.into()),
+                Part::Two => Ok(part2.into()),
+            })
+            .collect())
+    }
+}
+
+/// One line of the strategy guide. The second column is read as our move
+/// in part 1 and as the outcome we need in part 2.
+pub struct Round {
+    them: Move,
+    us: Move,
+    outcome: Outcome,
+}
+
+impl Round {
+    fn parse(mut line: Line) -> Result<Self, ParseError> {
+        let them = line.one_of(&[('A', Move::Rock), ('B', Move::Paper), ('C', Move::Scissors)])?;
+        line.expect(" ")?;
+        let (us, outcome) = line.one_of(&[
+            ('X', (Move::Rock, Outcome::Lose)),
+            ('Y', (Move::Paper, Outcome::Draw)),
+            ('Z', (Move::Scissors, Outcome::Win)),
+        ])?;
+        line.finish()?;
+        Ok(Round { them, us, outcome })
+    }
+
+    fn score_part1(&self) -> u32 {
+        Outcome::from_game(self.us, self.them) as u32 + self.us as u32
+    }
+
+    fn score_part2(&self) -> u32 {
+        let us = Move::from_their_move_and_outcome(self.them, self.outcome);
+        self.outcome as u32 + us as u32
+    }
+}
+
+/// Totals are `u64`s, since even a `u32` overflows on inputs of a few
+/// gigabytes
+pub fn score_part1(rounds: &[Round]) -> u64 {
+    rounds.iter().fold(0, |total, round| {
+        overflow::add(total, round.score_part1().into())
+    })
+}
+
+pub fn score_part2(rounds: &[Round]) -> u64 {
+    rounds.iter().fold(0, |total, round| {
+        overflow::add(total, round.score_part2().into())
+    })
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(i32)]
+enum Move {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+#[repr(i32)]
+enum Outcome {
+    Lose = 0,
+    Draw = 3,
+    Win = 6,
+}
+
+impl Outcome {
+    fn from_game(us: Move, them: Move) -> Self {
+        if us == them {
+            Outcome::Draw
+        } else if us.beats() == them {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        }
+    }
+}
+
+impl Move {
+    fn beats(&self) -> Move {
+        use Move::*;
+
+        match self {
+            Rock => Scissors,
+            Paper => Rock,
+            Scissors => Paper,
+        }
+    }
+
+    fn loses_to(&self) -> Move {
+        use Move::*;
+
+        match self {
+            Rock => Paper,
+            Paper => Scissors,
+            Scissors => Rock,
+        }
+    }
+
+    fn from_their_move_and_outcome(them: Move, outcome: Outcome) -> Self {
+        match outcome {
+            Outcome::Lose => them.beats(),
+            Outcome::Draw => them,
+            Outcome::Win => them.loses_to(),
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+use std::io::BufRead;
+
+use crate::overflow;
+use crate::parse::{self, Line, ParseError};
+use crate::stream::{self, StreamError, Streaming};
+use crate::{Answer, Context, Part, Solution};
+
+pub const INPUT: &str = include_str!("../inputs/day3.txt");
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut lines = parse::lines(input);
+        let mut backpacks = Vec::new();
+        let mut group = Vec::with_capacity(3);
+        for line in lines.by_ref() {
+            let backpack = parse_backpack(line)?;
+            add_to_group(line, backpack, &mut group)?;
+            backpacks.push(backpack.to_string());
+        }
+        if !group.is_empty() {
+            return Err(lines.end_of_input("the rest of a group of three"));
+        }
+        Ok(backpacks)
+    }
+
+    fn part1(backpacks: &Self::Parsed, _context: &Context) -> Result<Answer, String> {
+        Ok(total_priority(backpacks).into())
+    }
+
+    fn part2(backpacks: &Self::Parsed, _context: &Context) -> Option<Result<Answer, String>> {
+        Some(Ok(total_group_priority(backpacks).into()))
+    }
+}
+
+impl Streaming for Day3 {
+    fn stream(
+        input: &mut dyn BufRead,
+        parts: &[Part],
+        _context: &Context,
+    ) -> Result<Vec<Result<Answer, String>>, StreamError> {
+        let (mut part1, mut part2): (u64, u64) = (0, 0);
+        // The backpacks of the group so far, which are checked even if only
+        // part 1 is solved, so the same inputs are accepted as by `parse`
+        let mut group = Vec::with_capacity(3);
+        let mut lines = 0;
+        stream::for_each_line(input, |line| {
+            lines = line.line_number();
+            let backpack = parse_backpack(line)?;
+            part1 = overflow::add(part1, shared_item_priority(backpack).into());
+            if let Some(badge) = add_to_group(line, backpack, &mut group)? {
+                part2 = overflow::add(part2, priority(badge).into());
+            }
+            Ok(())
+        })?;
+        if !group.is_empty() {
+            return Err(ParseError::end_of_input(lines + 1, "the rest of a group of three").into());
+        }
+        Ok(parts
+            .iter()
+            .map(|part| match part {
+                Part::One => Ok(part1.into()),
+                Part::Two => Ok(part2.into()),
+            })
+            .collect())
+    }
+}
+
+fn parse_backpack<'a>(mut line: Line<'a>) -> Result<&'a str, ParseError> {
+    let items = line.take_while(|c| c.is_ascii_alphabetic());
+    if !line.rest().is_empty() {
+        return Err(line.error("an item letter"));
+    }
+    if !items.len().is_multiple_of(2) {
+        return Err(line.error_at(
+            1,
+            items,
+            "an even number of items to split between two compartments",
+        ));
+    }
+    if shared_item(items).is_none() {
+        return Err(line.error_at(1, items, "an item in both of the backpack's compartments"));
+    }
+    Ok(items)
+}
+
+/// Adds the backpack on `line` to the `group` so far, returning the group's
+/// badge once it has all three backpacks, which must have one
+fn add_to_group(
+    line: Line,
+    backpack: &str,
+    group: &mut Vec<String>,
+) -> Result<Option<char>, ParseError> {
+    group.push(backpack.to_string());
+    if group.len() < 3 {
+        return Ok(None);
+    }
+    let badge = badge(group).ok_or_else(|| {
+        line.error_at(
+            1,
+            backpack,
+            "an item shared with the other two backpacks in its group",
+        )
+    })?;
+    group.clear();
+    Ok(Some(badge))
+}
+
+/// Totals are `u64`s, like day 2's, for inputs of a few gigabytes
+pub fn total_priority(backpacks: &[String]) -> u64 {
+    backpacks.iter().fold(0, |total, backpack| {
+        overflow::add(total, shared_item_priority(backpack).into())
+    })
+}
+
+/// The priority of the item in both of the backpack's compartments
+fn shared_item_priority(backpack: &str) -> u32 {
+    priority(shared_item(backpack).expect("checked while parsing"))
+}
+
+/// The item in both of the backpack's compartments, if there is one
+fn shared_item(backpack: &str) -> Option<char> {
+    let compartment_size = backpack.len() / 2;
+    let (left, right) = backpack.split_at(compartment_size);
+    let left: HashSet<char> = left.chars().collect();
+    right.chars().find(|c| left.contains(c))
+}
+
+pub fn total_group_priority(backpacks: &[String]) -> u64 {
+    group_elves(backpacks).into_iter().fold(0, |total, badge| {
+        overflow::add(total, priority(badge).into())
+    })
+}
+
+fn group_elves(backpacks: &[String]) -> Vec<char> {
+    backpacks
+        .chunks(3)
+        .map(|group| badge(group).expect("checked while parsing"))
+        .collect()
+}
+
+/// The only item in all three of a group's backpacks, if there is one
+fn badge(group: &[String]) -> Option<char> {
+    let first = unique_letters(&group[0]);
+    let second = unique_letters(&group[1]);
+    let third = unique_letters(&group[2]);
+    let shared_items = first
+        .intersection(&second)
+        .copied()
+        .collect::<HashSet<char>>();
+    shared_items.intersection(&third).next().copied()
+}
+
+fn unique_letters(backpack: &str) -> HashSet<char> {
+    backpack.chars().collect()
+}
+
+fn priority(c: char) -> u32 {
+    if c.is_ascii_uppercase() {
+        27 + c as u32 - 'A' as u32
+    } else {
+        1 + c as u32 - 'a' as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn priorities() {
+        assert_eq!(priority('a'), 1);
+        assert_eq!(priority('z'), 26);
+        assert_eq!(priority('A'), 27);
+        assert_eq!(priority('Z'), 52);
+    }
+
+    #[test]
+    fn streaming_errors_like_parse() {
+        let day = crate::get_day(3).unwrap();
+        let context = Context::with_defaults(day);
+        let inputs = [
+            // No item in both compartments
+            "abcd",
+            // A group that's too short, and one without a badge
+            "abca\nabca",
+            "abca\nabca\nxyzx",
+        ];
+        for input in inputs {
+            let parsed = day.parse(input).err().unwrap();
+            for parts in [&[Part::One][..], &Part::ALL] {
+                let streamed = day
+                    .stream(&mut input.as_bytes(), parts, &context)
+                    .unwrap()
+                    .unwrap_err();
+                assert_eq!(streamed.to_string(), parsed.to_string());
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::io::BufRead;
+
+use crate::parse::{self, Line, ParseError};
+use crate::stream::{self, StreamError, Streaming};
+use crate::{Answer, Context, Part, Solution};
+
+pub const INPUT: &str = include_str!("../inputs/day4.txt");
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Parsed = Vec<Pair>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse::lines(input).map(Pair::parse).collect()
+    }
+
+    fn part1(pairs: &Self::Parsed, _context: &Context) -> Result<Answer, String> {
+        Ok(pairs_that_contain_others(pairs).into())
+    }
+
+    fn part2(pairs: &Self::Parsed, _context: &Context) -> Option<Result<Answer, String>> {
+        Some(Ok(pairs_that_overlap(pairs).into()))
+    }
+}
+
+impl Streaming for Day4 {
+    fn stream(
+        input: &mut dyn BufRead,
+        parts: &[Part],
+        _context: &Context,
+    ) -> Result<Vec<Result<Answer, String>>, StreamError> {
+        let (mut containing, mut overlapping) = (0usize, 0usize);
+        stream::for_each_line(input, |line| {
+            let pair = Pair::parse(line)?;
+            containing += usize::from(pair.one_contains_other());
+            overlapping += usize::from(pair.first.overlaps(&pair.second));
+            Ok(())
+        })?;
+        Ok(parts
+            .iter()
+            .map(|part| match part {
+                Part::One => Ok(containing.into()),
+                Part::Two => Ok(overlapping.into()),
+            })
+            .collect())
+    }
+}
+
+pub fn pairs_that_contain_others(pairs: &[Pair]) -> usize {
+    pairs
+        .iter()
+        .filter(|pair| pair.one_contains_other())
+        .count()
+}
+
+pub fn pairs_that_overlap(pairs: &[Pair]) -> usize {
+    pairs
+        .iter()
+        .filter(|pair| pair.first.overlaps(&pair.second))
+        .count()
+}
+
+struct Range {
+    from: u32,
+    to: u32,
+}
+
+impl Range {
+    fn contains(&self, other: &Range) -> bool {
+        self.from <= other.from && self.to >= other.to
+    }
+
+    fn overlaps(&self, other: &Range) -> bool {
+        self.from <= other.to && self.to >= other.from
+    }
+
+    fn parse(line: &mut Line) -> Result<Self, ParseError> {
+        let from = line.number()?;
+        line.expect("-")?;
+        let to = line.number()?;
+        Ok(Range { from, to })
+    }
+}
+
+pub struct Pair {
+    first: Range,
+    second: Range,
+}
+
+impl Pair {
+    fn parse(mut line: Line) -> Result<Self, ParseError> {
+        let first = Range::parse(&mut line)?;
+        line.expect(",")?;
+        let second = Range::parse(&mut line)?;
+        line.finish()?;
+        Ok(Pair { first, second })
+    }
+
+    fn one_contains_other(&self) -> bool {
+        self.first.contains(&self.second) || self.second.contains(&self.first)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_pair() {
+        let error = Day4::parse("2-4,6-8\n2-3;4-5").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 4: expected `,`, found `;`"
+        );
+    }
+}
@@ -0,0 +1,318 @@
+use std::collections::VecDeque;
+
+use crate::json::Json;
+use crate::log::Level;
+use crate::parse::{self, Line, Lines, ParseError};
+use crate::trace::{self, Event};
+use crate::{Answer, Context, Solution};
+
+pub const INPUT: &str = include_str!("../inputs/day5.txt");
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Parsed = Procedure;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut lines = parse::lines(input);
+        let stacks = parse_starting_position(&mut lines)?;
+        match lines.next() {
+            Some(line) if !line.is_empty() => return Err(line.error("an empty line")),
+            _ => {}
+        }
+        // How many crates each stack has after each instruction, so none
+        // can move more crates than there are
+        let mut heights: Vec<usize> = stacks.iter().map(Stack::len).collect();
+        let instructions = lines
+            .map(|line| parse_instruction(line, &mut heights))
+            .collect::<Result<_, _>>()?;
+        Ok(Procedure {
+            stacks,
+            instructions,
+        })
+    }
+
+    fn part1(procedure: &Self::Parsed, _context: &Context) -> Result<Answer, String> {
+        Ok(apply_instructions(procedure, true).into())
+    }
+
+    fn part2(procedure: &Self::Parsed, _context: &Context) -> Option<Result<Answer, String>> {
+        Some(Ok(apply_instructions(procedure, false).into()))
+    }
+}
+
+pub struct Procedure {
+    stacks: Vec<Stack>,
+    /// Quantity, from and to (0-based) stacks
+    instructions: Vec<(usize, usize, usize)>,
+}
+
+/// The front represents the bottom of the stack
+type Stack = VecDeque<char>;
+
+/// The crane moving crates from one stack to another
+struct Move {
+    count: usize,
+    /// Stack numbers from 1, as in the input
+    from: usize,
+    to: usize,
+    /// The crates being moved, from bottom to top
+    crates: String,
+}
+
+impl Event for Move {
+    fn name(&self) -> &'static str {
+        "move"
+    }
+
+    fn fields(&self, object: Json) -> Json {
+        object
+            .with("count", self.count)
+            .with("from", self.from)
+            .with("to", self.to)
+            .with("crates", self.crates.as_str())
+    }
+}
+
+/// Parses the drawing of the stacks, up to and including the line of stack numbers
+fn parse_starting_position(lines: &mut Lines) -> Result<Vec<Stack>, ParseError> {
+    let mut stacks: Vec<Stack> = Vec::new();
+
+    for mut line in lines.by_ref() {
+        if line.rest().trim_start().starts_with('1') {
+            // This is the line of stack numbers, which says how many stacks there are
+            let mut count = 0;
+            loop {
+                line.take_while(|c| c == ' ');
+                if line.rest().is_empty() {
+                    break;
+                }
+                line.number::<usize>()?;
+                count += 1;
+            }
+            if count < stacks.len() {
+                return Err(line.error(format!("{} stack numbers", stacks.len())));
+            }
+            stacks.resize_with(count, Default::default);
+            return Ok(stacks);
+        }
+
+        let mut stack_num = 0;
+        while !line.rest().is_empty() {
+            if stack_num > 0 {
+                line.expect(" ")?;
+            }
+            if line.eat("[") {
+                let crate_id = line.take_while(|c| c.is_alphabetic());
+                let mut crate_id = crate_id.chars();
+                let (Some(crate_id), None) = (crate_id.next(), crate_id.next()) else {
+                    return Err(line.error("a single crate letter"));
+                };
+                line.expect("]")?;
+                if stack_num >= stacks.len() {
+                    stacks.resize_with(stack_num + 1, Default::default);
+                }
+                stacks[stack_num].push_front(crate_id);
+            } else if !line.eat("   ") {
+                return Err(line.error("a crate like `[A]` or an empty space"));
+            }
+            stack_num += 1;
+        }
+    }
+
+    Err(lines.end_of_input("the line of stack numbers"))
+}
+
+/// Parses a move, checking it against the `heights` of the stacks, which it
+/// then updates
+fn parse_instruction(
+    mut line: Line,
+    heights: &mut [usize],
+) -> Result<(usize, usize, usize), ParseError> {
+    let stacks = heights.len();
+    let stack_number = |line: &mut Line| {
+        let column = line.column();
+        let number: usize = line.number()?;
+        if number == 0 || number > stacks {
+            return Err(line.error_at(
+                column,
+                number.to_string(),
+                format!("a stack number from 1 to {stacks}"),
+            ));
+        }
+        Ok(number - 1)
+    };
+
+    line.expect("move ")?;
+    let quantity_column = line.column();
+    let quantity: usize = line.number()?;
+    line.expect(" from ")?;
+    let from = stack_number(&mut line)?;
+    line.expect(" to ")?;
+    let to = stack_number(&mut line)?;
+    line.finish()?;
+
+    if quantity > heights[from] {
+        return Err(line.error_at(
+            quantity_column,
+            quantity.to_string(),
+            format!("at most the {} crates on stack {}", heights[from], from + 1),
+        ));
+    }
+    heights[from] -= quantity;
+    heights[to] += quantity;
+    Ok((quantity, from, to))
+}
+
+pub fn apply_instructions(procedure: &Procedure, move_crates_one_at_a_time: bool) -> String {
+    top_of_each_stack(&rearrange(procedure, move_crates_one_at_a_time))
+}
+
+/// The stacks after every step of the procedure
+fn rearrange(procedure: &Procedure, move_crates_one_at_a_time: bool) -> Vec<Stack> {
+    let mut stacks = procedure.stacks.clone();
+
+    for &(quantity, from, to) in &procedure.instructions {
+        trace::emit(|| Move {
+            count: quantity,
+            from: from + 1,
+            to: to + 1,
+            crates: stacks[from].iter().rev().take(quantity).rev().collect(),
+        });
+        if move_crates_one_at_a_time {
+            for _i in 0..quantity {
+                if let Some(crate_id) = stacks[from].pop_back() {
+                    stacks[to].push_back(crate_id);
+                }
+            }
+        } else {
+            let index = stacks[from].len() - quantity;
+            let moving_crates = stacks[from].split_off(index);
+            stacks[to].extend(moving_crates);
+        }
+    }
+    if crate::log_enabled!(Level::Debug) {
+        crate::debug!(
+            "the stacks once they've been rearranged:\n{}",
+            render_stacks(&stacks)
+        );
+    }
+    stacks
+}
+
+fn top_of_each_stack(stacks: &[Stack]) -> String {
+    stacks
+        .iter()
+        .map(|stack| stack.back().unwrap_or(&' '))
+        .collect()
+}
+
+/// Draws the stacks the way the puzzle does, with their numbers underneath
+fn render_stacks(stacks: &[Stack]) -> String {
+    let height = stacks.iter().map(Stack::len).max().unwrap_or(0);
+    let mut lines: Vec<String> = (0..height)
+        .rev()
+        .map(|level| {
+            let crates: Vec<_> = stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(crate_id) => format!("[{crate_id}]"),
+                    None => "   ".to_string(),
+                })
+                .collect();
+            crates.join(" ").trim_end().to_string()
+        })
+        .collect();
+    let numbers: Vec<_> = (1..=stacks.len())
+        .map(|number| format!(" {number} "))
+        .collect();
+    lines.push(numbers.join(" ").trim_end().to_string());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot;
+
+    #[test]
+    fn parsing_starting_position() {
+        let input = "[D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3 ";
+        let stacks = parse_starting_position(&mut parse::lines(input)).unwrap();
+        assert_eq!(stacks.len(), 3);
+        assert_eq!(stacks[0], vec!['Z', 'N', 'D']);
+        assert_eq!(stacks[1], vec!['M', 'C']);
+        assert_eq!(stacks[2], vec!['P']);
+
+        let input = "        [Z]
+        [N]
+    [C] [D]
+    [M] [P]
+ 1   2   3";
+        let stacks = parse_starting_position(&mut parse::lines(input)).unwrap();
+        assert_eq!(stacks.len(), 3);
+        assert_eq!(stacks[0], &[]);
+        assert_eq!(stacks[1], &['M', 'C']);
+        assert_eq!(stacks[2], &['P', 'D', 'N', 'Z'])
+    }
+
+    #[test]
+    fn instruction_for_missing_stack() {
+        let input = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 4";
+        let error = Day5::parse(input).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 6, column 18: expected a stack number from 1 to 3, found `4`"
+        );
+    }
+
+    #[test]
+    fn moving_more_crates_than_a_stack_has() {
+        // Rejected while parsing, since neither part could follow it
+        let input = "[A]
+[B] [C]
+ 1   2
+
+move 3 from 1 to 2";
+        let error = Day5::parse(input).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 5, column 6: expected at most the 2 crates on stack 1, found `3`"
+        );
+
+        // Crates that have been moved onto a stack count
+        let input = input.replace(
+            "move 3 from 1 to 2",
+            "move 1 from 2 to 1\nmove 3 from 1 to 2",
+        );
+        assert!(Day5::parse(&input).is_ok());
+        let input = input + "\nmove 1 from 1 to 2";
+        let error = Day5::parse(&input).err().unwrap();
+        assert_eq!(error.line, 7);
+    }
+
+    #[test]
+    fn snapshots() {
+        for (name, input) in snapshot::inputs(5) {
+            let procedure = Day5::parse(input).unwrap();
+            let start = render_stacks(&procedure.stacks);
+            snapshot::assert_snapshot(&format!("{name}_stacks"), &start);
+            // The drawing reads back as the same stacks
+            let redrawn = parse_starting_position(&mut parse::lines(&start)).unwrap();
+            assert_eq!(redrawn, procedure.stacks);
+
+            let part1 = render_stacks(&rearrange(&procedure, true));
+            snapshot::assert_snapshot(&format!("{name}_stacks_part1"), &part1);
+            let part2 = render_stacks(&rearrange(&procedure, false));
+            snapshot::assert_snapshot(&format!("{name}_stacks_part2"), &part2);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::HashSet;
+use std::io::BufRead;
+
+use crate::differential::Implementation;
+use crate::params::Param;
+use crate::parse::{self, ParseError};
+use crate::stream::{self, StreamError, Streaming};
+use crate::{Answer, Context, Part, Solution};
+
+pub const INPUT: &str = include_str!("../inputs/day6.txt");
+
+pub struct Day6;
+
+/// A marker can't be longer than the alphabet, since its characters are all
+/// different
+const MAX_MARKER: i64 = 26;
+
+impl Solution for Day6 {
+    type Parsed = String;
+
+    const PARAMS: &'static [Param] = &[
+        Param::new(
+            "packet_marker",
+            4,
+            "distinct characters in a start-of-packet marker",
+        )
+        .at_least(1)
+        .at_most(MAX_MARKER),
+        Param::new(
+            "message_marker",
+            14,
+            "distinct characters in a start-of-message marker",
+        )
+        .at_least(1)
+        .at_most(MAX_MARKER),
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut lines = parse::lines(input);
+        let Some(mut line) = lines.next() else {
+            return Err(ParseError::end_of_input(1, "a datastream"));
+        };
+        let datastream = line.take_while(|c| c.is_ascii_lowercase());
+        if !line.rest().is_empty() {
+            return Err(line.error("a lowercase letter"));
+        }
+        if let Some(line) = lines.find(|line| !line.is_empty()) {
+            return Err(line.error("a single line datastream"));
+        }
+        Ok(datastream.to_string())
+    }
+
+    fn part1(datastream: &Self::Parsed, context: &Context) -> Result<Answer, String> {
+        let marker_len = context.param("packet_marker");
+        marker_answer(find_marker(datastream, marker_len), marker_len)
+    }
+
+    fn part2(datastream: &Self::Parsed, context: &Context) -> Option<Result<Answer, String>> {
+        let marker_len = context.param("message_marker");
+        Some(marker_answer(
+            find_marker(datastream, marker_len),
+            marker_len,
+        ))
+    }
+}
+
+/// The answer for a marker that ends at `end`, if there is one
+fn marker_answer(end: Option<usize>, marker_len: usize) -> Result<Answer, String> {
+    end.map(Answer::from)
+        .ok_or_else(|| format!("there's no marker of {marker_len} different characters"))
+}
+
+impl Streaming for Day6 {
+    fn stream(
+        input: &mut dyn BufRead,
+        parts: &[Part],
+        context: &Context,
+    ) -> Result<Vec<Result<Answer, String>>, StreamError> {
+        let mut finders: Vec<_> = parts
+            .iter()
+            .map(|part| {
+                let marker_len = match part {
+                    Part::One => context.param("packet_marker"),
+                    Part::Two => context.param("message_marker"),
+                };
+                (MarkerFinder::new(marker_len), marker_len, None)
+            })
+            .collect();
+
+        // The datastream can be too long to hold as a single line, so it's
+        // read straight out of the buffer up to the first character that
+        // isn't part of it
+        let mut len = 0;
+        loop {
+            let buffer = input.fill_buf()?;
+            let letters = buffer
+                .iter()
+                .position(|byte| !byte.is_ascii_lowercase())
+                .unwrap_or(buffer.len());
+            for &byte in &buffer[..letters] {
+                len += 1;
+                for (finder, _, found) in &mut finders {
+                    if found.is_none() && finder.push(byte) {
+                        *found = Some(len);
+                    }
+                }
+            }
+            input.consume(letters);
+            if letters == 0 {
+                break;
+            }
+        }
+
+        let mut lines = 0;
+        stream::for_each_line(input, |line| {
+            lines = line.line_number();
+            if line.is_empty() {
+                Ok(())
+            } else if lines == 1 {
+                // The rest of the datastream's line
+                let error = line.error("a lowercase letter");
+                Err(ParseError {
+                    column: len + error.column,
+                    ..error
+                })
+            } else {
+                Err(line.error("a single line datastream"))
+            }
+        })?;
+        if len == 0 && lines == 0 {
+            return Err(ParseError::end_of_input(1, "a datastream").into());
+        }
+        Ok(finders
+            .into_iter()
+            .map(|(_, marker_len, found)| marker_answer(found, marker_len))
+            .collect())
+    }
+}
+
+/// Every implementation of [`find_marker`], which are checked against each
+/// other in the tests
+pub const FIND_MARKER: &[Implementation<(String, usize), Option<usize>>] = &[
+    Implementation::new("naive", |(datastream, marker_len)| {
+        find_marker_naive(datastream, *marker_len)
+    }),
+    Implementation::new("sliding window", |(datastream, marker_len)| {
+        find_marker(datastream, *marker_len)
+    }),
+];
+
+/// The number of characters up to and including the first `marker_len`
+/// that are all different
+pub fn find_marker(input: &str, marker_len: usize) -> Option<usize> {
+    let mut finder = MarkerFinder::new(marker_len);
+    input
+        .bytes()
+        .position(|byte| finder.push(byte))
+        .map(|end| end + 1)
+}
+
+/// A window over the last `marker_len` characters of a datastream, which
+/// are fed to it one at a time
+struct MarkerFinder {
+    /// The characters in the window, starting anywhere and wrapping around
+    window: Vec<u8>,
+    /// How many characters have been pushed
+    pushed: usize,
+    /// How often each byte appears in the window
+    counts: [usize; 256],
+    /// How many bytes appear in the window more than once
+    repeated: usize,
+}
+
+impl MarkerFinder {
+    fn new(marker_len: usize) -> Self {
+        assert!(marker_len > 0, "a marker can't be empty");
+        MarkerFinder {
+            window: vec![0; marker_len],
+            pushed: 0,
+            counts: [0; 256],
+            repeated: 0,
+        }
+    }
+
+    /// Moves the window on by `byte`, returning whether it's now a marker
+    fn push(&mut self, byte: u8) -> bool {
+        let marker_len = self.window.len();
+        let slot = &mut self.window[self.pushed % marker_len];
+        if self.pushed >= marker_len {
+            let leaving = *slot as usize;
+            self.counts[leaving] -= 1;
+            if self.counts[leaving] == 1 {
+                self.repeated -= 1;
+            }
+        }
+        *slot = byte;
+        self.counts[byte as usize] += 1;
+        if self.counts[byte as usize] == 2 {
+            self.repeated += 1;
+        }
+        self.pushed += 1;
+        self.pushed >= marker_len && self.repeated == 0
+    }
+}
+
+/// Checks every window separately
+pub fn find_marker_naive(input: &str, marker_len: usize) -> Option<usize> {
+    input
+        .as_bytes()
+        .windows(marker_len)
+        .position(|window| HashSet::<u8>::from_iter(window.iter().copied()).len() == marker_len)
+        .map(|i| i + marker_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differential;
+
+    #[test]
+    fn implementations_agree() {
+        differential::assert_agree(FIND_MARKER, |rng| {
+            // Few letters so markers are rare
+            let len = rng.below(40);
+            let letters = 1 + rng.below(8);
+            let datastream = (0..len)
+                .map(|_| (b'a' + rng.below(letters) as u8) as char)
+                .collect();
+            (datastream, 1 + rng.below(6))
+        });
+    }
+
+    #[test]
+    fn streaming_errors_like_parse() {
+        let day = crate::get_day(6).unwrap();
+        let context = Context::with_defaults(day);
+        for input in ["", "mjqjp5qmgb", "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n\nabc"] {
+            let streamed = day
+                .stream(&mut input.as_bytes(), &Part::ALL, &context)
+                .unwrap()
+                .unwrap_err();
+            let parsed = day.parse(input).err().unwrap();
+            assert_eq!(streamed.to_string(), parsed.to_string());
+        }
+    }
+
+    #[test]
+    fn marker_longer_than_datastream() {
+        let day = crate::get_day(6).unwrap();
+        let context = Context::with_defaults(day);
+        let error = Err("there's no marker of 14 different characters".to_string());
+        let parsed = day.parse("abcdefg").unwrap();
+        assert_eq!(day.solve(Part::Two, &parsed, &context), Some(error.clone()));
+        let streamed = day
+            .stream(&mut "abcdefg".as_bytes(), &[Part::Two], &context)
+            .unwrap()
+            .unwrap();
+        assert_eq!(streamed, [error]);
+    }
+}
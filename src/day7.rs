@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::explore::{Explore, Query};
+use crate::overflow::{self, Unsigned, UNSIGNED_MAX};
+use crate::params::Param;
+use crate::parse::{self, ParseError};
+use crate::{Answer, Context, Solution};
+
+pub const INPUT: &str = include_str!("../inputs/day7.txt");
+
+pub struct Day7;
+
+type Size = Unsigned;
+
+impl Solution for Day7 {
+    /// The total size of each directory
+    type Parsed = HashMap<PathBuf, Size>;
+
+    const PARAMS: &'static [Param] = &[
+        Param::new(
+            "small_directory_size",
+            100000,
+            "directories smaller than this count towards part 1",
+        )
+        .at_most(UNSIGNED_MAX),
+        Param::new("disk_size", 70000000, "the size of the filesystem").at_most(UNSIGNED_MAX),
+        Param::new("update_size", 30000000, "the unused space the update needs")
+            .at_most(UNSIGNED_MAX),
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        disk_usage(input)
+    }
+
+    fn part1(directories: &Self::Parsed, context: &Context) -> Result<Answer, String> {
+        Ok(
+            total_size_of_small_directories(directories, context.param("small_directory_size"))
+                .into(),
+        )
+    }
+
+    fn part2(directories: &Self::Parsed, context: &Context) -> Option<Result<Answer, String>> {
+        Some(
+            smallest_directory_to_delete(
+                directories,
+                context.param("disk_size"),
+                context.param("update_size"),
+            )
+            .map(Answer::from),
+        )
+    }
+}
+
+impl Explore for Day7 {
+    type Session = ();
+
+    const QUERIES: &'static [Query] = &[
+        Query::new("size", &["PATH"], "the total size of a directory"),
+        Query::new(
+            "ls",
+            &["PATH"],
+            "the directories in a directory, with their sizes",
+        ),
+    ];
+
+    fn query(
+        directories: &Self::Parsed,
+        _session: &mut (),
+        name: &str,
+        args: &[&str],
+    ) -> Result<String, String> {
+        let path = Path::new(args[0]);
+        let Some(size) = directories.get(path) else {
+            return Err(format!("there's no directory `{}`", args[0]));
+        };
+        match name {
+            "size" => Ok(size.to_string()),
+            _ => {
+                let mut children: Vec<_> = directories
+                    .iter()
+                    .filter(|(directory, _)| directory.parent() == Some(path))
+                    .collect();
+                children.sort();
+                Ok(children
+                    .into_iter()
+                    .map(|(directory, size)| format!("{size:>10} {}", directory.display()))
+                    .collect::<Vec<_>>()
+                    .join("\n"))
+            }
+        }
+    }
+
+    fn complete(
+        directories: &Self::Parsed,
+        _name: &str,
+        _index: usize,
+        prefix: &str,
+    ) -> Vec<String> {
+        // One directory at a time, like a shell
+        let Some(slash) = prefix.rfind('/') else {
+            return ["/"]
+                .into_iter()
+                .filter(|root| root.starts_with(prefix))
+                .map(String::from)
+                .collect();
+        };
+        let (parent, name) = prefix.split_at(slash + 1);
+        directories
+            .keys()
+            .filter(|directory| directory.parent() == Some(Path::new(parent)))
+            .filter_map(|directory| directory.file_name()?.to_str())
+            .filter(|directory| directory.starts_with(name))
+            .map(|directory| format!("{parent}{directory}/"))
+            .collect()
+    }
+}
+
+pub fn total_size_of_small_directories(
+    directories: &HashMap<PathBuf, Size>,
+    small_directory_size: Size,
+) -> Size {
+    directories
+        .values()
+        .copied()
+        .filter(|&size| size < small_directory_size)
+        .fold(0, overflow::add)
+}
+
+pub fn smallest_directory_to_delete(
+    directories: &HashMap<PathBuf, Size>,
+    disk_size: Size,
+    update_size: Size,
+) -> Result<Size, String> {
+    let mut sizes: Vec<Size> = directories.values().copied().collect();
+    sizes.sort();
+
+    let Some(&total_used) = directories.get(Path::new("/")) else {
+        return Err("there's no `/` to find the disk usage of".to_string());
+    };
+    let available_space = disk_size.saturating_sub(total_used);
+    let to_delete = update_size.saturating_sub(available_space);
+
+    sizes
+        .into_iter()
+        .find(|size| *size > to_delete)
+        .ok_or_else(|| {
+            format!("no directory is bigger than the {to_delete} that has to be deleted")
+        })
+}
+
+pub fn disk_usage(input: &str) -> Result<HashMap<PathBuf, Size>, ParseError> {
+    let mut path = PathBuf::new();
+    let mut directories = HashMap::new();
+
+    for mut line in parse::lines(input) {
+        if line.eat("$ ") {
+            if line.eat("cd ") {
+                let directory = line.take_rest();
+                if directory.is_empty() {
+                    return Err(line.error("a directory name"));
+                } else if directory == ".." {
+                    path.pop();
+                } else {
+                    path.push(directory);
+                    directories.insert(path.clone(), 0);
+                }
+            } else {
+                line.expect("ls")?;
+                line.finish()?;
+            }
+        } else if line.eat("dir ") {
+            if line.rest().is_empty() {
+                return Err(line.error("a directory name"));
+            }
+        } else if line.peek_char().is_some_and(|c| c.is_ascii_digit()) {
+            if path.as_os_str().is_empty() {
+                return Err(line.error_at(1, line.text(), "`$ cd` before the first file"));
+            }
+            let size = line.number::<Size>()?;
+            line.expect(" ")?;
+            if line.rest().is_empty() {
+                return Err(line.error("a file name"));
+            }
+
+            // Add the file size to the current directory and all its ancestors.
+            for directory in path.ancestors() {
+                if let Some(total) = directories.get_mut(directory) {
+                    *total = overflow::add(*total, size);
+                }
+            }
+        } else {
+            return Err(line.error("a `$` command, `dir` or a file size"));
+        }
+    }
+
+    Ok(directories)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn mapping_directories() {
+        let directories = disk_usage(examples::input("day7")).unwrap();
+        assert_eq!(directories[Path::new("/")], 48381165);
+        assert_eq!(directories[Path::new("/a/e")], 584);
+        assert_eq!(directories[Path::new("/a")], 94853);
+        assert_eq!(directories[Path::new("/d")], 24933642);
+    }
+
+    #[test]
+    fn querying_directories() {
+        let directories = disk_usage(examples::input("day7")).unwrap();
+        assert_eq!(
+            Day7::query(&directories, &mut (), "size", &["/a/e"]).unwrap(),
+            "584"
+        );
+        assert_eq!(
+            Day7::query(&directories, &mut (), "ls", &["/"]).unwrap(),
+            "     94853 /a\n  24933642 /d"
+        );
+        assert_eq!(
+            Day7::query(&directories, &mut (), "size", &["/b"]).unwrap_err(),
+            "there's no directory `/b`"
+        );
+        assert_eq!(
+            Day7::query(&directories, &mut (), "size", &["/a/"]).unwrap(),
+            "94853"
+        );
+
+        let mut completions = Day7::complete(&directories, "size", 0, "/");
+        completions.sort();
+        assert_eq!(completions, ["/a/", "/d/"]);
+        assert_eq!(Day7::complete(&directories, "size", 0, "/a/"), ["/a/e/"]);
+        assert_eq!(Day7::complete(&directories, "size", 0, ""), ["/"]);
+    }
+
+    #[test]
+    fn no_directory_big_enough() {
+        let directories = disk_usage(examples::input("day7")).unwrap();
+        assert_eq!(
+            smallest_directory_to_delete(&directories, 70000000, 30000000),
+            Ok(24933642)
+        );
+        assert_eq!(
+            smallest_directory_to_delete(&directories, 70000000, 80000000),
+            Err("no directory is bigger than the 58381165 that has to be deleted".to_string())
+        );
+
+        let directories = disk_usage("$ cd a\n10 f").unwrap();
+        assert_eq!(
+            smallest_directory_to_delete(&directories, 70000000, 30000000),
+            Err("there's no `/` to find the disk usage of".to_string())
+        );
+    }
+}
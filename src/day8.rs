@@ -0,0 +1,199 @@
+use std::fmt;
+
+use crate::differential::Implementation;
+use crate::geometry::{Direction, Point};
+use crate::grid::{Coord, Grid};
+use crate::parse::ParseError;
+use crate::{Answer, Context, Solution};
+
+pub const INPUT: &str = include_str!("../inputs/day8.txt");
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Parsed = Trees;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_heights(input)
+    }
+
+    fn part1(trees: &Self::Parsed, _context: &Context) -> Result<Answer, String> {
+        Ok(count_visible(trees).into())
+    }
+
+    fn part2(trees: &Self::Parsed, _context: &Context) -> Option<Result<Answer, String>> {
+        Some(Ok(most_scenic_tree(trees).into()))
+    }
+}
+
+/// Tree heights from 0 to 9
+pub type Trees = Grid<u8>;
+
+pub fn count_visible(trees: &Trees) -> usize {
+    let visibility_map = create_visibility_map(trees);
+    visibility_map
+        .iter()
+        .filter(|(_, &visible)| visible)
+        .count()
+}
+
+fn parse_heights(input: &str) -> Result<Trees, ParseError> {
+    Grid::parse(input, "a tree height from 0 to 9", |_, c| {
+        // Calculate the height by subtracting the ASCII value of '0'
+        c.is_ascii_digit().then(|| c as u8 - b'0')
+    })
+}
+
+fn create_visibility_map(trees: &Trees) -> Grid<bool> {
+    let mut visibility_map = Grid::new(trees.width(), trees.height(), false);
+
+    // A tree is visible from outside the grid if it's taller than every
+    // tree before it in a row or column, so look along each of them from
+    // both ends
+    for y in 0..trees.height() as i64 {
+        let row = || (0..trees.width() as i64).map(move |x| Point::new(x, y));
+        apply_visibility(trees, &mut visibility_map, row());
+        apply_visibility(trees, &mut visibility_map, row().rev());
+    }
+    for x in 0..trees.width() as i64 {
+        let column = || (0..trees.height() as i64).map(move |y| Point::new(x, y));
+        apply_visibility(trees, &mut visibility_map, column());
+        apply_visibility(trees, &mut visibility_map, column().rev());
+    }
+
+    visibility_map
+}
+
+fn apply_visibility(
+    trees: &Trees,
+    visibility_map: &mut Grid<bool>,
+    line_of_sight: impl Iterator<Item = Coord>,
+) {
+    // Trees on the edge are always visible
+    let mut tallest_so_far = None;
+    for position in line_of_sight {
+        let height = trees[position];
+        if tallest_so_far < Some(height) {
+            tallest_so_far = Some(height);
+            visibility_map[position] = true;
+        }
+    }
+}
+
+/// Every implementation of [`most_scenic_tree`], which are checked against
+/// each other in the tests
+pub const MOST_SCENIC_TREE: &[Implementation<Trees, usize>] = &[
+    Implementation::new("naive", most_scenic_tree_naive),
+    Implementation::new("last seen heights", most_scenic_tree),
+];
+
+/// The highest scenic score of any tree: the product of how many trees it
+/// can see in each direction
+pub fn most_scenic_tree(trees: &Trees) -> usize {
+    scenic_scores(trees)
+        .iter()
+        .map(|(_, &score)| score)
+        .max()
+        .unwrap()
+}
+
+/// Every tree's scenic score
+fn scenic_scores(trees: &Trees) -> Grid<usize> {
+    let mut scores = Grid::new(trees.width(), trees.height(), 1);
+
+    // Walking along each row and column from both ends, a tree can see back
+    // to the last tree at least as tall as itself, or to the edge
+    let mut look_back = |line_of_sight: &mut dyn Iterator<Item = Coord>| {
+        let mut last_seen: [Option<usize>; 10] = [None; 10];
+        for (distance, position) in line_of_sight.enumerate() {
+            let height = trees[position] as usize;
+            let blocked_at = last_seen[height..].iter().flatten().max();
+            scores[position] *= blocked_at.map_or(distance, |at| distance - at);
+            last_seen[height] = Some(distance);
+        }
+    };
+    for y in 0..trees.height() as i64 {
+        let row = || (0..trees.width() as i64).map(move |x| Point::new(x, y));
+        look_back(&mut row());
+        look_back(&mut row().rev());
+    }
+    for x in 0..trees.width() as i64 {
+        let column = || (0..trees.height() as i64).map(move |y| Point::new(x, y));
+        look_back(&mut column());
+        look_back(&mut column().rev());
+    }
+
+    scores
+}
+
+/// Looks from every tree in each direction until the view is blocked
+pub fn most_scenic_tree_naive(trees: &Trees) -> usize {
+    trees
+        .iter()
+        .map(|(position, &height)| {
+            // Count the trees in each direction up to and including the
+            // first one that blocks the view
+            Direction::ALL
+                .iter()
+                .map(|direction| {
+                    let mut viewing_distance = 0;
+                    for (_, &tree) in trees.ray(position, direction.step()) {
+                        viewing_distance += 1;
+                        if tree >= height {
+                            break;
+                        }
+                    }
+                    viewing_distance
+                })
+                .product()
+        })
+        .max()
+        .unwrap()
+}
+
+/// Draws a grid of numbers, like the trees' scenic scores, in columns as
+/// wide as the widest number
+pub fn render_map<T: fmt::Display>(values: &Grid<T>) -> String {
+    let width = values
+        .iter()
+        .map(|(_, value)| value.to_string().len())
+        .max()
+        .unwrap_or(0);
+    let rows: Vec<_> = values
+        .rows()
+        .map(|row| {
+            let row: Vec<_> = row.iter().map(|value| format!("{value:>width$}")).collect();
+            row.join(" ")
+        })
+        .collect();
+    rows.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{differential, snapshot};
+
+    #[test]
+    fn implementations_agree() {
+        differential::assert_agree(MOST_SCENIC_TREE, |rng| {
+            let (width, height) = (1 + rng.below(8), 1 + rng.below(8));
+            let cells = (0..width * height).map(|_| rng.below(10) as u8).collect();
+            Grid::from_cells(width, cells)
+        });
+    }
+
+    #[test]
+    fn snapshots() {
+        for (name, input) in snapshot::inputs(8) {
+            let trees = parse_heights(input).unwrap();
+            let visible = create_visibility_map(&trees).render(|&visible| match visible {
+                true => '#',
+                false => '.',
+            });
+            snapshot::assert_snapshot(&format!("{name}_visible"), &visible);
+            let scores = render_map(&scenic_scores(&trees));
+            snapshot::assert_snapshot(&format!("{name}_scores"), &scores);
+        }
+    }
+}
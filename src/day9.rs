@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::iter::once;
+
+use crate::geometry::{Direction, Point};
+use crate::json::Json;
+use crate::overflow::SmallSigned;
+use crate::params::Param;
+use crate::parse::{self, Line, ParseError};
+use crate::stream::{self, StreamError, Streaming};
+use crate::trace::{self, Event};
+use crate::{Answer, Context, Part, Solution};
+
+type Position = Point<SmallSigned>;
+
+pub const INPUT: &str = include_str!("../inputs/day9.txt");
+
+pub struct Day9;
+
+/// The longest tail, since every knot follows the head on every step
+const MAX_TAIL: i64 = 10_000;
+
+impl Solution for Day9 {
+    /// Each motion of the head as a direction and how many steps to take
+    type Parsed = Vec<(Direction, usize)>;
+
+    const PARAMS: &'static [Param] = &[
+        Param::new("part1_tail", 1, "knots following the head in part 1")
+            .at_least(1)
+            .at_most(MAX_TAIL),
+        Param::new("part2_tail", 9, "knots following the head in part 2")
+            .at_least(1)
+            .at_most(MAX_TAIL),
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_head_motions(input)
+    }
+
+    fn part1(motions: &Self::Parsed, context: &Context) -> Result<Answer, String> {
+        Ok(unique_tail_positions(motions, context.param("part1_tail")).into())
+    }
+
+    fn part2(motions: &Self::Parsed, context: &Context) -> Option<Result<Answer, String>> {
+        Some(Ok(unique_tail_positions(
+            motions,
+            context.param("part2_tail"),
+        )
+        .into()))
+    }
+}
+
+impl Streaming for Day9 {
+    /// The positions each tail has visited still grow with the input, as far
+    /// as the rope wanders rather than with the number of moves, since
+    /// they're what the answers count
+    fn stream(
+        input: &mut dyn BufRead,
+        parts: &[Part],
+        context: &Context,
+    ) -> Result<Vec<Result<Answer, String>>, StreamError> {
+        let mut ropes: Vec<_> = parts
+            .iter()
+            .map(|part| match part {
+                Part::One => Rope::new(context.param("part1_tail")),
+                Part::Two => Rope::new(context.param("part2_tail")),
+            })
+            .collect();
+        stream::for_each_line(input, |line| {
+            let (direction, distance) = parse_head_motion(line)?;
+            for rope in &mut ropes {
+                for _ in 0..distance {
+                    rope.pull(direction);
+                }
+            }
+            Ok(())
+        })?;
+        Ok(ropes
+            .iter()
+            .map(|rope| Ok(rope.visited.len().into()))
+            .collect())
+    }
+}
+
+/// Every knot's position after the head takes a step
+struct Step {
+    direction: Direction,
+    /// The head first
+    knots: Vec<Position>,
+}
+
+impl Event for Step {
+    fn name(&self) -> &'static str {
+        "step"
+    }
+
+    fn fields(&self, object: Json) -> Json {
+        let direction = match self.direction {
+            Direction::Up => "U",
+            Direction::Down => "D",
+            Direction::Left => "L",
+            Direction::Right => "R",
+        };
+        object
+            .with("direction", direction)
+            .with("knots", self.knots.clone())
+    }
+}
+
+pub fn unique_tail_positions(motions: &[(Direction, usize)], tail_length: usize) -> usize {
+    let mut rope = Rope::new(tail_length);
+    for &(direction, distance) in motions {
+        for _ in 0..distance {
+            rope.pull(direction);
+        }
+    }
+    rope.visited.len()
+}
+
+/// A rope that's pulled around by its head, keeping track of where the last
+/// knot of its tail has been
+struct Rope {
+    head: Position,
+    /// The knots following the head, in order
+    tail: Vec<Position>,
+    visited: HashSet<Position>,
+}
+
+impl Rope {
+    fn new(tail_length: usize) -> Self {
+        Rope {
+            head: Position::default(),
+            tail: vec![Position::default(); tail_length],
+            visited: HashSet::from([Position::default()]),
+        }
+    }
+
+    /// Moves the head one step, with each knot of the tail following the
+    /// one in front of it
+    fn pull(&mut self, direction: Direction) {
+        self.head += direction.step();
+        let mut next = self.head;
+        for knot in &mut self.tail {
+            *knot = move_tail(*knot, next);
+            next = *knot;
+        }
+        self.visited.insert(next);
+        trace::emit(|| Step {
+            direction,
+            knots: once(self.head).chain(self.tail.iter().copied()).collect(),
+        });
+    }
+}
+
+fn parse_head_motions(input: &str) -> Result<Vec<(Direction, usize)>, ParseError> {
+    parse::lines(input).map(parse_head_motion).collect()
+}
+
+fn parse_head_motion(mut line: Line) -> Result<(Direction, usize), ParseError> {
+    let direction = line.one_of(&[
+        ('R', Direction::Right),
+        ('L', Direction::Left),
+        ('U', Direction::Up),
+        ('D', Direction::Down),
+    ])?;
+    line.expect(" ")?;
+    let distance = line.number()?;
+    line.finish()?;
+    Ok((direction, distance))
+}
+
+fn move_tail(tail: Position, head: Position) -> Position {
+    if tail.chebyshev_distance(head) > 1 {
+        // No longer touching, so move one step closer, diagonally if the
+        // head isn't in the same row or column
+        tail + (head - tail).signum()
+    } else {
+        // Stay put
+        tail
+    }
+}
@@ -0,0 +1,447 @@
+//! Differential testing: running several implementations of the same thing,
+//! like a naive solution and an optimised one, on random inputs and
+//! checking they agree.
+//!
+//! Days list their implementations as [`Implementation`]s and their tests
+//! call [`assert_agree`] with a generator for inputs. When the
+//! implementations disagree, the input is shrunk to a minimal one they still
+//! disagree on, which is usually much easier to debug than the random one.
+
+use std::any::Any;
+use std::cell::Cell;
+use std::env;
+use std::fmt::{self, Debug};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use crate::geometry::{Number, Point};
+use crate::grid::Grid;
+use crate::random::Rng;
+
+/// The environment variable that overrides [`Settings::seed`], to reproduce
+/// a failure or try different inputs
+pub const SEED_VAR: &str = "AOC_SEED";
+
+/// One way of computing something, checked against the others
+pub struct Implementation<I, O> {
+    pub name: &'static str,
+    pub run: fn(&I) -> O,
+}
+
+impl<I, O> Implementation<I, O> {
+    pub const fn new(name: &'static str, run: fn(&I) -> O) -> Self {
+        Implementation { name, run }
+    }
+}
+
+/// Values that can be made smaller, to turn a random failing input into a
+/// minimal one
+pub trait Shrink: Clone {
+    /// Smaller versions of this value, the most aggressive first. Every
+    /// candidate must be strictly smaller, so shrinking always ends.
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! shrink_integers {
+    ($($int:ty),*) => {$(
+        impl Shrink for $int {
+            /// Zero, then closer and closer to the value itself
+            fn shrink(&self) -> Vec<Self> {
+                let mut smaller = Vec::new();
+                if *self != 0 {
+                    smaller.push(0);
+                }
+                let mut step = *self / 2;
+                while step != 0 {
+                    smaller.push(*self - step);
+                    step /= 2;
+                }
+                smaller
+            }
+        }
+    )*};
+}
+
+shrink_integers!(u8, u16, u32, u64, usize, i8, i16, i32, i64);
+
+/// Everything left after removing a chunk of `items`, trying big chunks
+/// first
+fn remove_chunks<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut smaller = Vec::new();
+    let mut size = items.len();
+    while size > 0 {
+        for start in (0..=items.len() - size).step_by(size) {
+            smaller.push([&items[..start], &items[start + size..]].concat());
+        }
+        size /= 2;
+    }
+    smaller
+}
+
+impl<T: Shrink> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut smaller = remove_chunks(self);
+        for (index, item) in self.iter().enumerate() {
+            for item in item.shrink() {
+                let mut candidate = self.clone();
+                candidate[index] = item;
+                smaller.push(candidate);
+            }
+        }
+        smaller
+    }
+}
+
+impl Shrink for String {
+    fn shrink(&self) -> Vec<Self> {
+        let chars: Vec<char> = self.chars().collect();
+        remove_chunks(&chars)
+            .into_iter()
+            .map(String::from_iter)
+            .collect()
+    }
+}
+
+impl<A: Shrink, B: Shrink> Shrink for (A, B) {
+    fn shrink(&self) -> Vec<Self> {
+        let (a, b) = self;
+        let first = a.shrink().into_iter().map(|a| (a, b.clone()));
+        let second = b.shrink().into_iter().map(|b| (a.clone(), b));
+        first.chain(second).collect()
+    }
+}
+
+impl<A: Shrink, B: Shrink, C: Shrink> Shrink for (A, B, C) {
+    fn shrink(&self) -> Vec<Self> {
+        let (a, b, c) = self;
+        let first = a.shrink().into_iter().map(|a| (a, b.clone(), c.clone()));
+        let second = b.shrink().into_iter().map(|b| (a.clone(), b, c.clone()));
+        let third = c.shrink().into_iter().map(|c| (a.clone(), b.clone(), c));
+        first.chain(second).chain(third).collect()
+    }
+}
+
+impl<T: Number + Shrink> Shrink for Point<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let coords = (self.x, self.y).shrink().into_iter();
+        coords.map(|(x, y)| Point::new(x, y)).collect()
+    }
+}
+
+impl<T: Shrink> Shrink for Grid<T> {
+    /// Removes rows and columns, keeping at least one of each, then shrinks
+    /// single cells
+    fn shrink(&self) -> Vec<Self> {
+        let (width, height) = (self.width(), self.height());
+        let cells: Vec<T> = self.rows().flatten().cloned().collect();
+        let grid = |width, cells| Grid::from_cells(width, cells).with_origin(self.origin());
+        let without = |keep: &dyn Fn(usize) -> bool| {
+            let cells = cells.iter().enumerate();
+            cells
+                .filter(|&(index, _)| keep(index))
+                .map(|(_, cell)| cell.clone())
+                .collect()
+        };
+
+        let mut smaller = Vec::new();
+        if height > 1 {
+            for y in 0..height {
+                smaller.push(grid(width, without(&|index| index / width != y)));
+            }
+        }
+        if width > 1 {
+            for x in 0..width {
+                smaller.push(grid(width - 1, without(&|index| index % width != x)));
+            }
+        }
+        for (index, cell) in cells.iter().enumerate() {
+            for cell in cell.shrink() {
+                let mut candidate = cells.clone();
+                candidate[index] = cell;
+                smaller.push(grid(width, candidate));
+            }
+        }
+        smaller
+    }
+}
+
+/// What an implementation did with an input
+#[derive(Clone)]
+pub enum Outcome<O> {
+    Returned(O),
+    /// With the panic's message
+    Panicked(String),
+}
+
+impl<O: PartialEq> Outcome<O> {
+    /// Implementations agree if they return the same value or if they both
+    /// panic, whatever their messages
+    fn agrees_with(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Outcome::Returned(a), Outcome::Returned(b)) => a == b,
+            (Outcome::Panicked(_), Outcome::Panicked(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<O: Debug> Debug for Outcome<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Returned(value) => write!(f, "{value:?}"),
+            Outcome::Panicked(message) => write!(f, "panicked: {message}"),
+        }
+    }
+}
+
+thread_local! {
+    /// Whether this thread is running something whose panics are expected
+    /// and reported rather than printed
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+fn run<I, O>(implementation: &Implementation<I, O>, input: &I) -> Outcome<O> {
+    // Shrinking can panic hundreds of times
+    match catch_quietly(|| (implementation.run)(input)) {
+        Ok(value) => Outcome::Returned(value),
+        Err(message) => Outcome::Panicked(message),
+    }
+}
+
+/// Runs `f`, turning a panic into its message, with the panic hook quiet on
+/// this thread while leaving it alone everywhere else
+pub(crate) fn catch_quietly<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    static QUIET_HOOK: Once = Once::new();
+    QUIET_HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.get() {
+                hook(info);
+            }
+        }));
+    });
+
+    let was_quiet = QUIET.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    QUIET.set(was_quiet);
+    result.map_err(panic_message)
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "(not a string)".to_string(),
+        },
+    }
+}
+
+/// Every implementation's outcome, if any of them differ
+fn disagreement<I, O: PartialEq>(
+    implementations: &[Implementation<I, O>],
+    input: &I,
+) -> Option<Vec<(&'static str, Outcome<O>)>> {
+    let outcomes: Vec<_> = implementations
+        .iter()
+        .map(|implementation| (implementation.name, run(implementation, input)))
+        .collect();
+    let (_, first) = outcomes.first()?;
+    let agree = outcomes
+        .iter()
+        .all(|(_, outcome)| outcome.agrees_with(first));
+    (!agree).then_some(outcomes)
+}
+
+/// How many inputs to try and how hard to shrink them
+#[derive(Clone, Copy, Debug)]
+pub struct Settings {
+    pub cases: usize,
+    /// Seeds the generator for the first case. Defaults to [`SEED_VAR`] if
+    /// it's set, so failures can be reproduced.
+    pub seed: u64,
+    /// Stop shrinking after trying this many smaller inputs
+    pub max_shrinks: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        let seed = env::var(SEED_VAR).ok().and_then(|seed| seed.parse().ok());
+        Settings {
+            cases: 256,
+            seed: seed.unwrap_or(2022),
+            max_shrinks: 10_000,
+        }
+    }
+}
+
+/// An input the implementations didn't agree on
+pub struct Disagreement<I, O> {
+    pub seed: u64,
+    /// Which generated input failed, counting from 0
+    pub case: usize,
+    /// The smallest failing input found by shrinking the generated one
+    pub input: I,
+    pub outcomes: Vec<(&'static str, Outcome<O>)>,
+    /// How many smaller inputs were tried
+    pub shrinks: usize,
+}
+
+impl<I: Debug, O: Debug> fmt::Display for Disagreement<I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "implementations disagree on case {} with {SEED_VAR}={} (shrunk after trying {} inputs)",
+            self.case, self.seed, self.shrinks
+        )?;
+        writeln!(f, "input: {:?}", self.input)?;
+        for (name, outcome) in &self.outcomes {
+            writeln!(f, "  {name}: {outcome:?}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<I: Debug, O: Debug> Debug for Disagreement<I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Runs every implementation on `settings.cases` inputs from `generate`.
+/// Stops at the first input they disagree on, which is shrunk as far as
+/// possible.
+pub fn check<I, O>(
+    implementations: &[Implementation<I, O>],
+    settings: &Settings,
+    mut generate: impl FnMut(&mut Rng) -> I,
+) -> Result<(), Disagreement<I, O>>
+where
+    I: Shrink,
+    O: PartialEq,
+{
+    let mut rng = Rng::new(settings.seed);
+    for case in 0..settings.cases {
+        let mut input = generate(&mut rng);
+        let Some(mut outcomes) = disagreement(implementations, &input) else {
+            continue;
+        };
+
+        // Greedily take the first smaller input that still fails, until
+        // none of them do
+        let mut shrinks = 0;
+        'shrinking: while shrinks < settings.max_shrinks {
+            for candidate in input.shrink() {
+                shrinks += 1;
+                if let Some(candidate_outcomes) = disagreement(implementations, &candidate) {
+                    input = candidate;
+                    outcomes = candidate_outcomes;
+                    continue 'shrinking;
+                }
+                if shrinks == settings.max_shrinks {
+                    break;
+                }
+            }
+            break;
+        }
+
+        return Err(Disagreement {
+            seed: settings.seed,
+            case,
+            input,
+            outcomes,
+            shrinks,
+        });
+    }
+    Ok(())
+}
+
+/// Panics with a minimal counterexample if the implementations disagree on
+/// any input from `generate`, using the default [`Settings`]
+pub fn assert_agree<I, O>(
+    implementations: &[Implementation<I, O>],
+    generate: impl FnMut(&mut Rng) -> I,
+) where
+    I: Shrink + Debug,
+    O: PartialEq + Debug,
+{
+    assert!(
+        implementations.len() > 1,
+        "need at least two implementations to compare"
+    );
+    if let Err(disagreement) = check(implementations, &Settings::default(), generate) {
+        panic!("{disagreement}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(rng: &mut Rng) -> Vec<i64> {
+        let len = rng.below(20);
+        (0..len).map(|_| rng.range(0..=1000)).collect()
+    }
+
+    #[test]
+    fn shrinking_to_a_minimal_counterexample() {
+        let sums = [
+            Implementation::new("sum", |numbers: &Vec<i64>| numbers.iter().sum::<i64>()),
+            Implementation::new("capped", |numbers: &Vec<i64>| {
+                numbers.iter().map(|&number| number.min(100)).sum()
+            }),
+        ];
+        let disagreement = check(&sums, &Settings::default(), numbers).unwrap_err();
+        assert_eq!(disagreement.input, [101]);
+        assert_eq!(
+            disagreement.to_string().lines().skip(1).collect::<Vec<_>>(),
+            ["input: [101]", "  sum: 101", "  capped: 100"]
+        );
+
+        let sums = [
+            Implementation::new("sum", |numbers: &Vec<i64>| numbers.iter().sum::<i64>()),
+            Implementation::new("reversed", |numbers: &Vec<i64>| {
+                numbers.iter().rev().sum::<i64>()
+            }),
+        ];
+        assert!(check(&sums, &Settings::default(), numbers).is_ok());
+    }
+
+    #[test]
+    fn panics_are_outcomes() {
+        let quotients = [
+            Implementation::new("checked", |&(a, b): &(i64, i64)| a.checked_div(b)),
+            Implementation::new("unchecked", |&(a, b): &(i64, i64)| Some(a / b)),
+        ];
+        let disagreement = check(&quotients, &Settings::default(), |rng| {
+            (rng.range(-50..=50), rng.range(0..=5))
+        })
+        .unwrap_err();
+        assert_eq!(disagreement.input, (0, 0));
+        assert_eq!(
+            format!("{:?}", disagreement.outcomes[1].1),
+            "panicked: attempt to divide by zero"
+        );
+    }
+
+    #[test]
+    fn shrinking_grids() {
+        let grid = Grid::from_cells(2, vec![1u8, 2, 3, 4]);
+        let smaller = grid.shrink();
+        assert_eq!(smaller[0], Grid::from_cells(2, vec![3, 4]));
+        assert_eq!(smaller[2], Grid::from_cells(1, vec![2, 4]));
+        assert_eq!(smaller[4], Grid::from_cells(2, vec![0, 2, 3, 4]));
+        assert!(Grid::from_cells(1, vec![0u8]).shrink().is_empty());
+    }
+
+    #[test]
+    fn shrinking_values() {
+        assert_eq!(10u8.shrink(), [0, 5, 8, 9]);
+        assert_eq!((-5i32).shrink(), [0, -3, -4]);
+        assert_eq!(
+            vec![1u8, 2, 3].shrink()[..4],
+            [vec![], vec![2, 3], vec![1, 3], vec![1, 2]]
+        );
+        assert_eq!("abc".to_string().shrink()[0], "");
+    }
+}
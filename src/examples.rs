@@ -0,0 +1,641 @@
+//! The examples from the puzzle texts, with the answers the puzzles give for
+//! them.
+//!
+//! Every example gets a test for each part it has an answer for, and one
+//! that solves it again as saved by other editors, generated by
+//! [`examples!`], so adding an example only needs an entry here. Day tests
+//! that check intermediate results can share the inputs through [`get`].
+
+use crate::params::Params;
+use crate::Part;
+
+pub struct Example {
+    /// Also the name of the module with the example's tests
+    pub name: &'static str,
+    pub day: u8,
+    pub input: &'static str,
+    /// Parameters the example is solved with, where the puzzle uses smaller
+    /// numbers for the example than for the real input
+    pub params: &'static [(&'static str, i64)],
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+}
+
+impl Example {
+    /// The answer the puzzle gives for `part`, if it gives one
+    pub fn answer(&self, part: Part) -> Option<&'static str> {
+        match part {
+            Part::One => self.part1,
+            Part::Two => self.part2,
+        }
+    }
+
+    pub fn params(&self) -> Params {
+        let mut params = Params::new();
+        for &(name, value) in self.params {
+            if let Err(error) = params.set(self.day, name, value) {
+                panic!("example {}: {error}", self.name);
+            }
+        }
+        params
+    }
+}
+
+pub fn get(name: &str) -> Option<&'static Example> {
+    EXAMPLES.iter().find(|example| example.name == name)
+}
+
+pub fn for_day(day: u8) -> impl Iterator<Item = &'static Example> {
+    EXAMPLES.iter().filter(move |example| example.day == day)
+}
+
+/// Builds [`EXAMPLES`] from entries like
+///
+/// ```text
+/// day15 {
+///     day: 15,
+///     input: "...",
+///     params: [row = 10],
+///     part1: 26,
+///     part2: 56000011,
+/// }
+/// ```
+///
+/// where `params` and either part can be left out, and generates a test
+/// module named after each entry with a test for each part it has and one
+/// for the [`variants`] of its input.
+macro_rules! examples {
+    ($(
+        $name:ident {
+            day: $day:literal,
+            input: $input:literal,
+            $(params: [$($param:ident = $value:literal),* $(,)?],)?
+            $(part1: $part1:literal,)?
+            $(part2: $part2:literal,)?
+        }
+    )*) => {
+        pub static EXAMPLES: &[Example] = &[$(
+            Example {
+                name: stringify!($name),
+                day: $day,
+                input: $input,
+                params: &[$($((stringify!($param), $value)),*)?],
+                part1: examples!(@answer $($part1)?),
+                part2: examples!(@answer $($part2)?),
+            },
+        )*];
+
+        #[cfg(test)]
+        mod tests {$(
+            mod $name {
+                use crate::Part;
+
+                #[test]
+                fn variants() {
+                    super::super::check_variants(stringify!($name));
+                }
+
+                $(
+                    #[test]
+                    fn part1() {
+                        super::super::check(stringify!($name), Part::One, concat!($part1));
+                    }
+                )?
+
+                $(
+                    #[test]
+                    fn part2() {
+                        super::super::check(stringify!($name), Part::Two, concat!($part2));
+                    }
+                )?
+            }
+        )*}
+    };
+    (@answer) => {
+        None
+    };
+    (@answer $answer:literal) => {
+        Some(concat!($answer))
+    };
+}
+
+/// Solves `part` of an example and compares it with the puzzle's answer.
+/// Parts that haven't been solved yet pass, so their answer can be filled in
+/// ahead of the solution.
+#[cfg(test)]
+fn check(name: &str, part: Part, expected: &str) {
+    use crate::{get_day, Context};
+
+    let example = get(name).unwrap();
+    let day = get_day(example.day).unwrap();
+    let parsed = day
+        .parse(example.input)
+        .unwrap_or_else(|error| panic!("example {name}: {error}"));
+    let params = example.params();
+    let context = Context::new(day, &params);
+    if let Some(answer) = day.solve(part, &parsed, &context) {
+        assert_eq!(
+            answer.map(|answer| answer.to_string()),
+            Ok(expected.to_string())
+        );
+    }
+}
+
+/// The example's input as editors might save it, named for test failures
+#[cfg(test)]
+fn variants(input: &str) -> [(&'static str, String); 3] {
+    let crlf = input.replace('\n', "\r\n");
+    let trailing_whitespace: String = input.lines().map(|line| format!("{line} \t\n")).collect();
+    [
+        ("CRLF line endings", format!("{crlf}\r\n")),
+        ("a byte order mark", format!("\u{feff}{input}")),
+        (
+            "trailing whitespace",
+            format!("{trailing_whitespace}\n  \n"),
+        ),
+    ]
+}
+
+/// Solves each [variant](variants) of an example, parsed and streamed,
+/// checking it gets the same answers as the example
+#[cfg(test)]
+fn check_variants(name: &str) {
+    use crate::{get_day, Context};
+
+    let example = get(name).unwrap();
+    let day = get_day(example.day).unwrap();
+    let params = example.params();
+    let context = Context::new(day, &params);
+    for (variant, input) in variants(example.input) {
+        let parsed = day
+            .parse(&input)
+            .unwrap_or_else(|error| panic!("example {name} with {variant}: {error}"));
+        let streamed = day
+            .stream(&mut input.as_bytes(), &Part::ALL, &context)
+            .map(|answers| {
+                answers.unwrap_or_else(|error| {
+                    panic!("example {name} with {variant}, streamed: {error}")
+                })
+            });
+        for (index, part) in Part::ALL.into_iter().enumerate() {
+            let Some(expected) = example.answer(part) else {
+                continue;
+            };
+            if let Some(answer) = day.solve(part, &parsed, &context) {
+                let message = format!("example {name} part {part} with {variant}");
+                assert_eq!(
+                    answer.map(|answer| answer.to_string()),
+                    Ok(expected.to_string()),
+                    "{message}"
+                );
+            }
+            if let Some(answers) = &streamed {
+                let message = format!("example {name} part {part} with {variant}, streamed");
+                assert_eq!(
+                    answers[index].as_ref().map(ToString::to_string),
+                    Ok(expected.to_string()),
+                    "{message}"
+                );
+            }
+        }
+    }
+}
+
+/// The input of an example that's known to exist, for day tests
+#[cfg(test)]
+pub(crate) fn input(name: &str) -> &'static str {
+    get(name)
+        .unwrap_or_else(|| panic!("no example called {name}"))
+        .input
+}
+
+examples! {
+    day1 {
+        day: 1,
+        input: "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000",
+        part1: 24000,
+        part2: 45000,
+    }
+
+    day2 {
+        day: 2,
+        input: "A Y
+B X
+C Z",
+        part1: 15,
+        part2: 12,
+    }
+
+    day3 {
+        day: 3,
+        input: "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw",
+        part1: 157,
+        part2: 70,
+    }
+
+    day4 {
+        day: 4,
+        input: "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8",
+        part1: 2,
+        part2: 4,
+    }
+
+    day5 {
+        day: 5,
+        input: "    [D]
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2",
+        part1: "CMZ",
+        part2: "MCD",
+    }
+
+    day6 {
+        day: 6,
+        input: "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+        part1: 7,
+        part2: 19,
+    }
+
+    day6_bvwb {
+        day: 6,
+        input: "bvwbjplbgvbhsrlpgdmjqwftvncz",
+        part1: 5,
+        part2: 23,
+    }
+
+    day6_nppd {
+        day: 6,
+        input: "nppdvjthqldpwncqszvftbrmjlhg",
+        part1: 6,
+        part2: 23,
+    }
+
+    day6_nznr {
+        day: 6,
+        input: "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
+        part1: 10,
+        part2: 29,
+    }
+
+    day6_zcfz {
+        day: 6,
+        input: "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
+        part1: 11,
+        part2: 26,
+    }
+
+    day7 {
+        day: 7,
+        input: "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k",
+        part1: 95437,
+        part2: 24933642,
+    }
+
+    day8 {
+        day: 8,
+        input: "30373
+25512
+65332
+33549
+35390",
+        part1: 21,
+        part2: 8,
+    }
+
+    day9 {
+        day: 9,
+        input: "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2",
+        part1: 13,
+        part2: 1,
+    }
+
+    day9_larger {
+        day: 9,
+        input: "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20",
+        part2: 36,
+    }
+
+    day10 {
+        day: 10,
+        input: "addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop",
+        part1: 13140,
+        part2: "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....",
+    }
+
+    day11 {
+        day: 11,
+        input: "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1",
+        part1: 10605,
+        part2: 2713310158,
+    }
+
+    day12 {
+        day: 12,
+        input: "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi",
+        part1: 31,
+        part2: 29,
+    }
+
+    day13 {
+        day: 13,
+        input: "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]",
+        part1: 13,
+        part2: 140,
+    }
+
+    day14 {
+        day: 14,
+        input: "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9",
+        part1: 24,
+        part2: 93,
+    }
+
+    day15 {
+        day: 15,
+        input: "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3",
+        params: [row = 10, max_coord = 20],
+        part1: 26,
+        part2: 56000011,
+    }
+}
@@ -0,0 +1,98 @@
+//! Questions about a day's parsed input, for poking at it in `aoc repl`
+//! without solving the whole puzzle again, like the size of one directory
+//! in day 7.
+//!
+//! Days that have any implement [`Explore`] as well as [`Solution`], listing
+//! their [`Query`]s and answering them from the parsed input. Anything worth
+//! keeping between queries, like a search, goes in the REPL's session rather
+//! than the parsed input. Answers and errors are text for the user to read.
+
+use std::str::FromStr;
+
+use crate::Solution;
+
+/// A question that can be asked about a day's parsed input
+#[derive(Clone, Copy, Debug)]
+pub struct Query {
+    pub name: &'static str,
+    /// The names of the arguments it takes, like `["X", "Y"]`
+    pub args: &'static [&'static str],
+    pub help: &'static str,
+}
+
+impl Query {
+    pub const fn new(
+        name: &'static str,
+        args: &'static [&'static str],
+        help: &'static str,
+    ) -> Self {
+        Query { name, args, help }
+    }
+
+    /// How the query is written, like `steps X Y`
+    pub fn usage(&self) -> String {
+        let mut usage = self.name.to_string();
+        for arg in self.args {
+            usage.push(' ');
+            usage.push_str(arg);
+        }
+        usage
+    }
+}
+
+/// A [`Solution`] whose parsed input can be queried
+pub trait Explore: Solution {
+    /// What's kept between the queries of one session, like a search that
+    /// several queries look up their answers in. It starts out as the
+    /// default, so nothing is worked out until a query needs it.
+    type Session: Default + 'static;
+
+    const QUERIES: &'static [Query];
+
+    /// Answers the query called `name`, which is one of [`Self::QUERIES`],
+    /// with as many `args` as it takes
+    fn query(
+        parsed: &Self::Parsed,
+        session: &mut Self::Session,
+        name: &str,
+        args: &[&str],
+    ) -> Result<String, String>;
+
+    /// The values that start with `prefix` for argument `index` of the query
+    /// called `name`, for tab completion
+    fn complete(_parsed: &Self::Parsed, _name: &str, _index: usize, _prefix: &str) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Parses an argument, naming it in the error if it isn't valid
+pub fn arg<T: FromStr>(args: &[&str], index: usize, name: &str) -> Result<T, String> {
+    args[index]
+        .parse()
+        .map_err(|_| format!("{name} must be a number, not `{}`", args[index]))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{examples, get_day};
+
+    #[test]
+    fn querying_through_erased_day() {
+        let day = get_day(7).unwrap();
+        let parsed = day.parse(examples::input("day7")).unwrap();
+        let mut session = day.session();
+        let mut query = |name, args: &[&str]| day.query(&parsed, &mut session, name, args);
+        assert_eq!(query("size", &["/d"]).unwrap(), "24933642");
+        assert_eq!(query("size", &[]).unwrap_err(), "usage: size PATH");
+        assert_eq!(query("du", &["/"]).unwrap_err(), "day 7 has no query `du`");
+        assert_eq!(day.complete(&parsed, "ls", 0, "/"), ["/a/", "/d/"]);
+        assert!(day.complete(&parsed, "ls", 1, "/").is_empty());
+
+        let day = get_day(1).unwrap();
+        let parsed = day.parse(examples::input("day1")).unwrap();
+        assert!(day.queries.is_empty());
+        assert!(day
+            .query(&parsed, &mut day.session(), "size", &["/"])
+            .is_err());
+    }
+}
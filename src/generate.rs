@@ -0,0 +1,159 @@
+//! Random puzzle inputs for every day, for testing the parsers and solvers
+//! on more than the bundled inputs.
+//!
+//! Each day's generator writes input in the same format as the puzzle's,
+//! following the rules the puzzle text promises, like every rucksack having
+//! exactly one item in both compartments. The same day, seed and size
+//! always give the same input.
+
+mod day1;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day2;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod day8;
+mod day9;
+
+use crate::random::Rng;
+use crate::Day;
+
+/// Writes an input with roughly `size` of whatever the day's input is a
+/// list of: elves, lines, rows, monkeys and so on
+type Generator = fn(&mut Rng, usize) -> String;
+
+const GENERATORS: [Generator; 15] = [
+    day1::generate,
+    day2::generate,
+    day3::generate,
+    day4::generate,
+    day5::generate,
+    day6::generate,
+    day7::generate,
+    day8::generate,
+    day9::generate,
+    day10::generate,
+    day11::generate,
+    day12::generate,
+    day13::generate,
+    day14::generate,
+    day15::generate,
+];
+
+/// The size used when none is given, which makes inputs that are quick to
+/// solve and small enough to read
+pub const DEFAULT_SIZE: usize = 10;
+
+/// A random input for `day`. Some days need a minimum size to be valid,
+/// like day 1 needing at least three elves, so `size` may be rounded up.
+pub fn generate(day: &Day, seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    GENERATORS[day.number as usize - 1](&mut rng, size)
+}
+
+/// Joins lines into an input, with a newline after the last one like the
+/// bundled inputs
+fn lines(lines: impl IntoIterator<Item = String>) -> String {
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+/// Shuffles `items` in place
+fn shuffle<T>(rng: &mut Rng, items: &mut [T]) {
+    for index in (1..items.len()).rev() {
+        items.swap(index, rng.below(index + 1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_day, Answer, Context, Part, DAYS};
+
+    #[test]
+    fn generated_inputs_parse() {
+        for day in &DAYS {
+            for seed in 0..20 {
+                for size in [0, 1, 5, DEFAULT_SIZE, 40] {
+                    let input = generate(day, seed, size);
+                    if let Err(error) = day.parse(&input) {
+                        panic!("seed {seed}, size {size}: {error}\n{input}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        for day in &DAYS {
+            for seed in 0..5 {
+                let parsed = day.parse(&generate(day, seed, DEFAULT_SIZE)).unwrap();
+                let context = Context::with_defaults(day);
+                for part in Part::ALL {
+                    // Part 2 of day 15 searches millions of rows, so it's
+                    // only checked once below
+                    if (day.number, part) == (15, Part::Two) {
+                        continue;
+                    }
+                    // Parts that haven't been solved yet have no answer for
+                    // any input
+                    if day.solve(part, &parsed, &context).is_none()
+                        && day
+                            .solve(part, &day.parse(day.input).unwrap(), &context)
+                            .is_some()
+                    {
+                        panic!("day {}, part {part}, seed {seed}: no answer", day.number);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generated_distress_beacon() {
+        let day = get_day(15).unwrap();
+        let parsed = day.parse(&generate(day, 0, DEFAULT_SIZE)).unwrap();
+        let context = Context::with_defaults(day);
+        let Some(Ok(Answer::Number(frequency))) = day.solve(Part::Two, &parsed, &context) else {
+            panic!("no tuning frequency");
+        };
+        let (x, y) = (frequency / 4000000, frequency % 4000000);
+        assert!((0..=4000000).contains(&x));
+        let mut session = day.session();
+        let mut covers = |x: i64, y: i64| {
+            day.query(
+                &parsed,
+                &mut session,
+                "covers",
+                &[&x.to_string(), &y.to_string()],
+            )
+        };
+        assert_eq!(
+            covers(x, y).unwrap(),
+            format!("no sensor covers ({x}, {y})")
+        );
+        for (x, y) in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
+            assert!(!covers(x, y).unwrap().starts_with("no sensor"));
+        }
+    }
+
+    #[test]
+    fn seeds_are_reproducible() {
+        for day in &DAYS {
+            assert_eq!(generate(day, 7, 5), generate(day, 7, 5));
+            assert_ne!(
+                generate(day, 7, 5),
+                generate(day, 8, 5),
+                "day {}",
+                day.number
+            );
+        }
+    }
+}
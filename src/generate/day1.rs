@@ -0,0 +1,17 @@
+use super::lines;
+use crate::random::Rng;
+
+/// Each elf's calories, with a blank line between elves. There are at least
+/// three elves, since part 2 adds up the top three.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut calories = Vec::new();
+    for elf in 0..size.max(3) {
+        if elf > 0 {
+            calories.push(String::new());
+        }
+        for _ in 0..1 + rng.below(15) {
+            calories.push(rng.range(1000..=70000).to_string());
+        }
+    }
+    lines(calories)
+}
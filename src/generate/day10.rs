@@ -0,0 +1,25 @@
+use super::lines;
+use crate::random::Rng;
+
+/// A program that runs for exactly 40 cycles per row of the CRT, with at
+/// least the six rows the puzzle draws. `addx` moves the sprite somewhere
+/// random on the screen.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let cycles = 40 * size.max(6);
+    let mut program = Vec::new();
+    let mut x: i64 = 1;
+    let mut cycle = 0;
+    while cycle < cycles {
+        if cycle + 2 <= cycles && rng.chance(0.6) {
+            let target = rng.range(0..=39);
+            let delta = if target == x { 1 } else { target - x };
+            program.push(format!("addx {delta}"));
+            x += delta;
+            cycle += 2;
+        } else {
+            program.push("noop".to_string());
+            cycle += 1;
+        }
+    }
+    lines(program)
+}
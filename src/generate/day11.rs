@@ -0,0 +1,108 @@
+use super::{lines, shuffle};
+use crate::random::Rng;
+
+const PRIMES: &[u64] = &[2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Add(u64),
+    Multiply(u64),
+    Square,
+}
+
+struct Monkey {
+    items: Vec<u64>,
+    operation: Operation,
+    divisor: u64,
+    if_true: usize,
+    if_false: usize,
+}
+
+/// At least two monkeys, and no more than there are different primes to
+/// test divisibility by. Worry levels in part 1 stay small enough for the
+/// solution, which is checked by playing the game and starting again if
+/// they don't.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = size.clamp(2, PRIMES.len());
+    let monkeys = loop {
+        let monkeys = monkeys(rng, count);
+        if fits_in_part1(&monkeys) {
+            break monkeys;
+        }
+    };
+
+    let mut output = Vec::new();
+    for (number, monkey) in monkeys.iter().enumerate() {
+        if number > 0 {
+            output.push(String::new());
+        }
+        let items: Vec<_> = monkey.items.iter().map(u64::to_string).collect();
+        let operation = match monkey.operation {
+            Operation::Add(number) => format!("+ {number}"),
+            Operation::Multiply(number) => format!("* {number}"),
+            Operation::Square => "* old".to_string(),
+        };
+        output.extend([
+            format!("Monkey {number}:"),
+            format!("  Starting items: {}", items.join(", ")),
+            format!("  Operation: new = old {operation}"),
+            format!("  Test: divisible by {}", monkey.divisor),
+            format!("    If true: throw to monkey {}", monkey.if_true),
+            format!("    If false: throw to monkey {}", monkey.if_false),
+        ]);
+    }
+    lines(output)
+}
+
+fn monkeys(rng: &mut Rng, count: usize) -> Vec<Monkey> {
+    let mut divisors = PRIMES.to_vec();
+    shuffle(rng, &mut divisors);
+    let other_monkey = |rng: &mut Rng, monkey: usize| (monkey + 1 + rng.below(count - 1)) % count;
+    // Like the puzzle, one monkey squares and a couple multiply, while the
+    // rest add, which lets the division by 3 keep worry levels down
+    let squaring = rng.below(count);
+    (0..count)
+        .map(|monkey| Monkey {
+            items: (0..rng.below(6))
+                .map(|_| rng.range(50..=99) as u64)
+                .collect(),
+            operation: if monkey == squaring {
+                Operation::Square
+            } else if rng.chance(0.25) {
+                Operation::Multiply(rng.range(2..=19) as u64)
+            } else {
+                Operation::Add(rng.range(1..=8) as u64)
+            },
+            divisor: divisors[monkey],
+            if_true: other_monkey(rng, monkey),
+            if_false: other_monkey(rng, monkey),
+        })
+        .collect()
+}
+
+/// Whether every worry level in the 20 rounds of part 1 fits in a `u32`
+fn fits_in_part1(monkeys: &[Monkey]) -> bool {
+    let mut items: Vec<Vec<u64>> = monkeys.iter().map(|monkey| monkey.items.clone()).collect();
+    for _ in 0..20 {
+        for (number, monkey) in monkeys.iter().enumerate() {
+            for worry in std::mem::take(&mut items[number]) {
+                let worry = match monkey.operation {
+                    Operation::Add(number) => worry + number,
+                    Operation::Multiply(number) => worry * number,
+                    Operation::Square => worry * worry,
+                };
+                if worry > u32::MAX as u64 {
+                    return false;
+                }
+                let worry = worry / 3;
+                let target = if worry % monkey.divisor == 0 {
+                    monkey.if_true
+                } else {
+                    monkey.if_false
+                };
+                items[target].push(worry);
+            }
+        }
+    }
+    true
+}
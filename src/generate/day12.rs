@@ -0,0 +1,59 @@
+use super::lines;
+use crate::random::Rng;
+
+/// A heightmap `size` rows high, with the start on the left edge and the
+/// best signal on the right. Neighbouring heights never differ by more than
+/// one, except around the start, so there's always a path.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // Wide enough to climb from `a` to `z` one step at a time
+    let (width, height) = (26 + size, size.max(1));
+    let start = (0, rng.below(height));
+    let end = (width - 1, rng.below(height));
+
+    // Noise on a slope up towards the end, which can't climb faster than
+    // one height per column
+    let mut heights: Vec<Vec<i64>> = (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|x| rng.range(0..=(25 * x / width) as i64))
+                .collect()
+        })
+        .collect();
+    heights[end.1][end.0] = 25;
+
+    // Raise anything more than one below a neighbour, until nothing changes
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for y in 0..height {
+            for x in 0..width {
+                let neighbours = [
+                    (x.wrapping_sub(1), y),
+                    (x + 1, y),
+                    (x, y.wrapping_sub(1)),
+                    (x, y + 1),
+                ];
+                let highest = neighbours
+                    .iter()
+                    .filter_map(|&(x, y)| heights.get(y)?.get(x))
+                    .max()
+                    .copied()
+                    .unwrap_or(0);
+                if heights[y][x] < highest - 1 {
+                    heights[y][x] = highest - 1;
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    lines((0..height).map(|y| {
+        (0..width)
+            .map(|x| match (x, y) {
+                _ if (x, y) == start => 'S',
+                _ if (x, y) == end => 'E',
+                _ => char::from(b'a' + heights[y][x] as u8),
+            })
+            .collect()
+    }))
+}
@@ -0,0 +1,77 @@
+use std::cmp::Ordering;
+
+use super::lines;
+use crate::random::Rng;
+
+enum Packet {
+    Integer(u8),
+    List(Vec<Packet>),
+}
+
+impl Packet {
+    fn random(rng: &mut Rng, depth: usize) -> Packet {
+        if depth > 0 && rng.chance(0.3) {
+            Packet::Integer(rng.below(11) as u8)
+        } else {
+            let len = if depth < 4 { rng.below(5) } else { 0 };
+            Packet::List((0..len).map(|_| Packet::random(rng, depth + 1)).collect())
+        }
+    }
+
+    /// Compares packets by the puzzle's rules
+    fn compare(&self, other: &Packet) -> Ordering {
+        match (self, other) {
+            (Packet::Integer(a), Packet::Integer(b)) => a.cmp(b),
+            (Packet::List(a), Packet::List(b)) => {
+                let mut orders = a.iter().zip(b).map(|(a, b)| a.compare(b));
+                let first_difference = orders.find(|order| order.is_ne());
+                first_difference.unwrap_or_else(|| a.len().cmp(&b.len()))
+            }
+            (Packet::Integer(a), list) => Packet::List(vec![Packet::Integer(*a)]).compare(list),
+            (list, Packet::Integer(b)) => list.compare(&Packet::List(vec![Packet::Integer(*b)])),
+        }
+    }
+
+    fn write(&self, output: &mut String) {
+        match self {
+            Packet::Integer(number) => output.push_str(&number.to_string()),
+            Packet::List(items) => {
+                output.push('[');
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        output.push(',');
+                    }
+                    item.write(output);
+                }
+                output.push(']');
+            }
+        }
+    }
+}
+
+/// `size` pairs of packets, with a blank line between pairs. No two
+/// packets are equal, including the divider packets part 2 adds, since the
+/// puzzle doesn't say how equal packets are ordered.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let divider = |number| Packet::List(vec![Packet::List(vec![Packet::Integer(number)])]);
+    let mut packets = vec![divider(2), divider(6)];
+    let mut output = Vec::new();
+    for pair in 0..size.max(1) {
+        if pair > 0 {
+            output.push(String::new());
+        }
+        for _ in 0..2 {
+            let packet = loop {
+                let packet = Packet::random(rng, 0);
+                if packets.iter().all(|other| packet.compare(other).is_ne()) {
+                    break packet;
+                }
+            };
+            let mut line = String::new();
+            packet.write(&mut line);
+            output.push(line);
+            packets.push(packet);
+        }
+    }
+    lines(output)
+}
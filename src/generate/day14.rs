@@ -0,0 +1,30 @@
+use super::lines;
+use crate::random::Rng;
+
+/// The column sand pours in from
+const SOURCE: i64 = 500;
+
+/// `size` paths of rock under the source. They're no wider than they are
+/// deep, so sand can never pile up to the source and block it.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let reach = 5 + size as i64;
+    let top = reach + 2;
+    lines((0..size.max(1)).map(|_| {
+        let mut point = (
+            rng.range(SOURCE - reach..=SOURCE + reach),
+            rng.range(top..=top + reach),
+        );
+        let mut path = vec![point];
+        for _ in 0..1 + rng.below(4) {
+            // Alternate between horizontal and vertical lines
+            if path.len() % 2 == 1 {
+                point.0 = rng.range(SOURCE - reach..=SOURCE + reach);
+            } else {
+                point.1 = rng.range(top..=top + reach);
+            }
+            path.push(point);
+        }
+        let points: Vec<_> = path.iter().map(|(x, y)| format!("{x},{y}")).collect();
+        points.join(" -> ")
+    }))
+}
@@ -0,0 +1,56 @@
+use super::{lines, shuffle};
+use crate::geometry::{Point, Vec2};
+use crate::random::Rng;
+
+/// The largest coordinate, matching part 2's search area
+const MAX_COORD: i64 = 4_000_000;
+
+/// Reports from at least four sensors, each naming the beacon closest to
+/// it. Ties are avoided, since the puzzle says there's only ever one
+/// closest beacon, and the sensors cover every spot in part 2's search
+/// area but one, where the distress beacon is.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let point = |rng: &mut Rng| Point::new(rng.range(0..=MAX_COORD), rng.range(0..=MAX_COORD));
+    let distress_beacon = point(rng);
+
+    // Four sensors diagonally out from the distress beacon, whose ranges
+    // meet around it. Each one covers a whole side of it, reaching past
+    // the search area, and has its beacon on the far side.
+    let reach = MAX_COORD + 1;
+    let mut sensors: Vec<(Point<i64>, Point<i64>)> = [(-1, -1), (1, 1), (-1, 1), (1, -1)]
+        .into_iter()
+        .map(|(x, y)| {
+            let sensor = distress_beacon + Vec2::new(x, y) * reach;
+            (sensor, sensor + Vec2::new(x * (2 * reach - 1), 0))
+        })
+        .collect();
+
+    // The rest go anywhere their beacon is the closest to them, without
+    // being closer to another sensor than its own beacon, and without
+    // reaching the distress beacon. Those four cover everything within
+    // twice the search area of it, so these are mostly outside.
+    let around = -3 * MAX_COORD..=4 * MAX_COORD;
+    while sensors.len() < size {
+        let sensor = Point::new(rng.range(around.clone()), rng.range(around.clone()));
+        let beacon = Point::new(rng.range(around.clone()), rng.range(around.clone()));
+        let distance = sensor.manhattan_distance(beacon);
+        let fits = distance > 0
+            && distance < sensor.manhattan_distance(distress_beacon)
+            && sensors.iter().all(|&(other, other_beacon)| {
+                other != sensor
+                    && sensor.manhattan_distance(other_beacon) > distance
+                    && other.manhattan_distance(beacon) > other.manhattan_distance(other_beacon)
+            });
+        if fits {
+            sensors.push((sensor, beacon));
+        }
+    }
+
+    shuffle(rng, &mut sensors);
+    lines(sensors.into_iter().map(|(sensor, beacon)| {
+        format!(
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+            sensor.x, sensor.y, beacon.x, beacon.y
+        )
+    }))
+}
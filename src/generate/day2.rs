@@ -0,0 +1,11 @@
+use super::lines;
+use crate::random::Rng;
+
+/// One round per line, like `A Y`
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    lines((0..size.max(1)).map(|_| {
+        let them = rng.choose(&['A', 'B', 'C']);
+        let us = rng.choose(&['X', 'Y', 'Z']);
+        format!("{them} {us}")
+    }))
+}
@@ -0,0 +1,76 @@
+use super::{lines, shuffle};
+use crate::random::Rng;
+
+const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Rucksacks in groups of three, each with exactly one item type in both
+/// compartments, and each group with exactly one badge that all three carry
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let groups = size.div_ceil(3).max(1);
+    lines((0..groups).flat_map(|_| group(rng)).collect::<Vec<_>>())
+}
+
+fn group(rng: &mut Rng) -> [String; 3] {
+    let badge = *rng.choose(ITEMS);
+
+    // Every other item can be carried by some of the elves, but not all
+    // three, so the badge is the only one they share
+    let mut carried_by: [Vec<u8>; 3] = Default::default();
+    for &item in ITEMS.iter().filter(|&&item| item != badge) {
+        let elves = rng.below(7);
+        for (elf, items) in carried_by.iter_mut().enumerate() {
+            if elves & (1 << elf) != 0 {
+                items.push(item);
+            }
+        }
+    }
+
+    carried_by.map(|mut items| {
+        shuffle(rng, &mut items);
+        // The item in both compartments, then items that can only go in
+        // the first or the second
+        let shared = items.pop().unwrap_or(badge);
+        let (first, second) = items.split_at(items.len() / 2);
+
+        let len = 2 + rng.below(15);
+        let mut compartments = [vec![shared], vec![shared]];
+        if shared != badge {
+            compartments[rng.below(2)].push(badge);
+        }
+        for (compartment, items) in compartments.iter_mut().zip([first, second]) {
+            while compartment.len() < len {
+                // Repeating the shared item is fine if there's nothing else
+                let item = if items.is_empty() {
+                    shared
+                } else {
+                    *rng.choose(items)
+                };
+                compartment.push(item);
+            }
+            shuffle(rng, compartment);
+        }
+        String::from_utf8(compartments.concat()).unwrap()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn one_shared_item() {
+        let mut rng = Rng::new(3);
+        let letters = |items: &str| items.chars().collect::<HashSet<_>>();
+        for _ in 0..100 {
+            let group = group(&mut rng);
+            for rucksack in &group {
+                let (first, second) = rucksack.split_at(rucksack.len() / 2);
+                assert_eq!(letters(first).intersection(&letters(second)).count(), 1);
+            }
+            let badges: HashSet<_> =
+                &(&letters(&group[0]) & &letters(&group[1])) & &letters(&group[2]);
+            assert_eq!(badges.len(), 1, "{group:?}");
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use super::lines;
+use crate::random::Rng;
+
+/// One pair of section ranges per line, like `2-4,6-8`
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut range = || {
+        let from = rng.range(1..=99);
+        let to = rng.range(from..=99);
+        format!("{from}-{to}")
+    };
+    lines((0..size.max(1)).map(|_| format!("{},{}", range(), range())))
+}
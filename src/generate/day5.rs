@@ -0,0 +1,54 @@
+use super::lines;
+use crate::random::Rng;
+
+/// A drawing of up to nine stacks of crates, then moves that never take more
+/// crates than a stack has
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let stack_count = 1 + rng.below(9);
+    let stacks: Vec<Vec<char>> = (0..stack_count)
+        .map(|_| {
+            (0..rng.below(8))
+                .map(|_| char::from(b'A' + rng.below(26) as u8))
+                .collect()
+        })
+        .collect();
+
+    let mut drawing = Vec::new();
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    for level in (0..height).rev() {
+        let row: Vec<_> = stacks
+            .iter()
+            .map(|stack| {
+                stack
+                    .get(level)
+                    .map_or("   ".to_string(), |c| format!("[{c}]"))
+            })
+            .collect();
+        drawing.push(row.join(" ").trim_end().to_string());
+    }
+    let numbers: Vec<_> = (1..=stack_count)
+        .map(|number| format!(" {number} "))
+        .collect();
+    drawing.push(numbers.join(" "));
+    drawing.push(String::new());
+
+    // Moves only need each stack's height to stay valid, which is the same
+    // whichever crane is moving them
+    let mut heights: Vec<usize> = stacks.iter().map(|stack| stack.len()).collect();
+    if stack_count > 1 && heights.iter().any(|&height| height > 0) {
+        for _ in 0..size {
+            let from = loop {
+                let from = rng.below(stack_count);
+                if heights[from] > 0 {
+                    break from;
+                }
+            };
+            let to = (from + 1 + rng.below(stack_count - 1)) % stack_count;
+            let quantity = 1 + rng.below(heights[from]);
+            heights[from] -= quantity;
+            heights[to] += quantity;
+            drawing.push(format!("move {quantity} from {} to {}", from + 1, to + 1));
+        }
+    }
+    lines(drawing)
+}
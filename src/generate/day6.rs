@@ -0,0 +1,23 @@
+use super::shuffle;
+use crate::random::Rng;
+
+/// Lowercase letters of roughly `size` characters, with markers for both
+/// parts. Before the message marker only a few letters are used, so markers
+/// don't turn up too early.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let letters = 2 + rng.below(12);
+    let random_letters = |rng: &mut Rng, len: usize, letters: usize| -> String {
+        (0..len)
+            .map(|_| char::from(b'a' + rng.below(letters) as u8))
+            .collect()
+    };
+
+    let mut marker: Vec<u8> = (b'a'..=b'z').collect();
+    shuffle(rng, &mut marker);
+    marker.truncate(14);
+    let marker = String::from_utf8(marker).unwrap();
+
+    let before = rng.below(size.max(1));
+    let after = size.saturating_sub(before + marker.len());
+    random_letters(rng, before, letters) + &marker + &random_letters(rng, after, 26) + "\n"
+}
@@ -0,0 +1,63 @@
+use std::collections::HashSet;
+
+use super::lines;
+use crate::random::Rng;
+
+const EXTENSIONS: &[&str] = &["", ".txt", ".dat", ".log", ".lst"];
+
+/// The terminal output of exploring a filesystem with `size` directories
+/// depth first, starting with `$ cd /`, listing every directory once and
+/// going back up with `$ cd ..`
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // Each directory's children, built by adding directories to random
+    // parents so the tree has some depth
+    let count = size.max(1);
+    let mut children = vec![Vec::new(); count];
+    for directory in 1..count {
+        children[rng.below(directory)].push(directory);
+    }
+
+    let mut output = vec!["$ cd /".to_string()];
+    explore(rng, &children, 0, &mut output);
+    lines(output)
+}
+
+fn explore(rng: &mut Rng, children: &[Vec<usize>], directory: usize, output: &mut Vec<String>) {
+    // Names are only unique within their directory, like in the puzzle
+    let mut names = HashSet::new();
+    let mut name = |rng: &mut Rng| loop {
+        let name: String = (0..1 + rng.below(8))
+            .map(|_| char::from(b'a' + rng.below(26) as u8))
+            .collect();
+        if names.insert(name.clone()) {
+            break name;
+        }
+    };
+
+    let subdirectories: Vec<_> = children[directory]
+        .iter()
+        .map(|&child| (child, name(rng)))
+        .collect();
+    let mut listing: Vec<_> = subdirectories
+        .iter()
+        .map(|(_, name)| format!("dir {name}"))
+        .collect();
+    // The root always has a file, so the disk isn't empty
+    let files = rng.below(4) + usize::from(directory == 0);
+    for _ in 0..files {
+        let size = rng.range(1..=300000);
+        listing.push(format!("{size} {}{}", name(rng), rng.choose(EXTENSIONS)));
+    }
+    listing.sort_by(|a, b| {
+        let name = |entry: &String| entry.split_once(' ').unwrap().1.to_string();
+        name(a).cmp(&name(b))
+    });
+
+    output.push("$ ls".to_string());
+    output.extend(listing);
+    for (child, name) in subdirectories {
+        output.push(format!("$ cd {name}"));
+        explore(rng, children, child, output);
+        output.push("$ cd ..".to_string());
+    }
+}
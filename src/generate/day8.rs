@@ -0,0 +1,12 @@
+use super::lines;
+use crate::random::Rng;
+
+/// A square of tree heights from 0 to 9, `size` trees across
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    lines((0..size).map(|_| {
+        (0..size)
+            .map(|_| char::from(b'0' + rng.below(10) as u8))
+            .collect()
+    }))
+}
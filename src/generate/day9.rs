@@ -0,0 +1,10 @@
+use super::lines;
+use crate::random::Rng;
+
+/// One motion of the head per line, like `R 4`
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    lines((0..size.max(1)).map(|_| {
+        let direction = rng.choose(&['R', 'L', 'U', 'D']);
+        format!("{direction} {}", 1 + rng.below(20))
+    }))
+}
@@ -0,0 +1,485 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::json::Json;
+use crate::overflow::{self, Int};
+
+/// The operations points and vectors need from their coordinates, which
+/// every primitive integer has. Their arithmetic goes through
+/// [`overflow`], so it can be checked.
+pub trait Number:
+    Copy
+    + Ord
+    + Default
+    + fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Int
+{
+}
+
+impl<T> Number for T where
+    T: Copy
+        + Ord
+        + Default
+        + fmt::Debug
+        + Add<Output = Self>
+        + Sub<Output = Self>
+        + Mul<Output = Self>
+        + Int
+{
+}
+
+/// Works for unsigned coordinates too, unlike `(a - b).abs()`
+#[track_caller]
+fn abs_diff<T: Number>(a: T, b: T) -> T {
+    if a > b {
+        overflow::sub(a, b)
+    } else {
+        overflow::sub(b, a)
+    }
+}
+
+/// A position on a 2D plane. `y` grows downwards, like the rows of a
+/// [`crate::grid::Grid`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// The difference between two [`Point`]s, like one step in a direction
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T: Number> Point<T> {
+    /// The distance moving only horizontally and vertically
+    #[track_caller]
+    pub fn manhattan_distance(self, other: Point<T>) -> T {
+        overflow::add(abs_diff(self.x, other.x), abs_diff(self.y, other.y))
+    }
+
+    /// The distance moving diagonally as well, like a king in chess
+    #[track_caller]
+    pub fn chebyshev_distance(self, other: Point<T>) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+
+    /// Converts the coordinates to another type, if they fit
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Point<U>> {
+        Some(Point::new(
+            U::try_from(self.x).ok()?,
+            U::try_from(self.y).ok()?,
+        ))
+    }
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl<T: Number> Vec2<T> {
+    #[track_caller]
+    pub fn manhattan_length(self) -> T {
+        overflow::add(
+            abs_diff(self.x, T::default()),
+            abs_diff(self.y, T::default()),
+        )
+    }
+
+    #[track_caller]
+    pub fn chebyshev_length(self) -> T {
+        abs_diff(self.x, T::default()).max(abs_diff(self.y, T::default()))
+    }
+
+    /// Shrinks each coordinate to -1, 0 or 1, leaving a single step
+    /// towards where the vector points
+    pub fn signum(self) -> Self
+    where
+        T: From<i8>,
+    {
+        let signum = |n: T| T::from(n.cmp(&T::default()) as i8);
+        Vec2::new(signum(self.x), signum(self.y))
+    }
+
+    /// Converts the coordinates to another type, if they fit
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Vec2<U>> {
+        Some(Vec2::new(
+            U::try_from(self.x).ok()?,
+            U::try_from(self.y).ok()?,
+        ))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// An `[x, y]` array
+impl<T: Into<Json>> From<Point<T>> for Json {
+    fn from(point: Point<T>) -> Self {
+        Json::Array(vec![point.x.into(), point.y.into()])
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Vec2::new(x, y)
+    }
+}
+
+impl<T: Number> Add<Vec2<T>> for Point<T> {
+    type Output = Point<T>;
+
+    #[track_caller]
+    fn add(self, step: Vec2<T>) -> Point<T> {
+        Point::new(overflow::add(self.x, step.x), overflow::add(self.y, step.y))
+    }
+}
+
+impl<T: Number> AddAssign<Vec2<T>> for Point<T> {
+    #[track_caller]
+    fn add_assign(&mut self, step: Vec2<T>) {
+        *self = *self + step;
+    }
+}
+
+impl<T: Number> Sub<Vec2<T>> for Point<T> {
+    type Output = Point<T>;
+
+    #[track_caller]
+    fn sub(self, step: Vec2<T>) -> Point<T> {
+        Point::new(overflow::sub(self.x, step.x), overflow::sub(self.y, step.y))
+    }
+}
+
+impl<T: Number> SubAssign<Vec2<T>> for Point<T> {
+    #[track_caller]
+    fn sub_assign(&mut self, step: Vec2<T>) {
+        *self = *self - step;
+    }
+}
+
+/// The vector from `other` to `self`
+impl<T: Number> Sub for Point<T> {
+    type Output = Vec2<T>;
+
+    #[track_caller]
+    fn sub(self, other: Point<T>) -> Vec2<T> {
+        Vec2::new(
+            overflow::sub(self.x, other.x),
+            overflow::sub(self.y, other.y),
+        )
+    }
+}
+
+impl<T: Number> Add for Vec2<T> {
+    type Output = Vec2<T>;
+
+    #[track_caller]
+    fn add(self, other: Vec2<T>) -> Vec2<T> {
+        Vec2::new(
+            overflow::add(self.x, other.x),
+            overflow::add(self.y, other.y),
+        )
+    }
+}
+
+impl<T: Number> Sub for Vec2<T> {
+    type Output = Vec2<T>;
+
+    #[track_caller]
+    fn sub(self, other: Vec2<T>) -> Vec2<T> {
+        Vec2::new(
+            overflow::sub(self.x, other.x),
+            overflow::sub(self.y, other.y),
+        )
+    }
+}
+
+impl<T: Number> Mul<T> for Vec2<T> {
+    type Output = Vec2<T>;
+
+    #[track_caller]
+    fn mul(self, factor: T) -> Vec2<T> {
+        Vec2::new(overflow::mul(self.x, factor), overflow::mul(self.y, factor))
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn neg(self) -> Vec2<T> {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+/// One of the four orthogonal directions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// A single step in this direction
+    pub fn step<T: From<i8>>(self) -> Vec2<T> {
+        let (x, y) = match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        };
+        Vec2::new(T::from(x), T::from(y))
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    /// Turns 90° clockwise
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    /// Turns 90° anticlockwise
+    pub fn turn_left(self) -> Self {
+        self.turn_right().opposite()
+    }
+}
+
+/// One of the eight directions to a surrounding cell, including diagonals
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    UpLeft,
+    Up,
+    UpRight,
+    Left,
+    Right,
+    DownLeft,
+    Down,
+    DownRight,
+}
+
+impl Direction8 {
+    /// In reading order, row by row
+    pub const ALL: [Direction8; 8] = [
+        Direction8::UpLeft,
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Left,
+        Direction8::Right,
+        Direction8::DownLeft,
+        Direction8::Down,
+        Direction8::DownRight,
+    ];
+
+    /// A single step in this direction
+    pub fn step<T: From<i8>>(self) -> Vec2<T> {
+        let (x, y) = match self {
+            Direction8::UpLeft => (-1, -1),
+            Direction8::Up => (0, -1),
+            Direction8::UpRight => (1, -1),
+            Direction8::Left => (-1, 0),
+            Direction8::Right => (1, 0),
+            Direction8::DownLeft => (-1, 1),
+            Direction8::Down => (0, 1),
+            Direction8::DownRight => (1, 1),
+        };
+        Vec2::new(T::from(x), T::from(y))
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Direction8::Up,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+            Direction::Right => Direction8::Right,
+        }
+    }
+}
+
+/// The smallest rectangle containing a set of points, including its edges
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bounds<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T: Number> Bounds<T> {
+    /// The bounds of a single point
+    pub fn new(point: Point<T>) -> Self {
+        Bounds {
+            min: point,
+            max: point,
+        }
+    }
+
+    /// Returns `None` if there are no points
+    pub fn of(points: impl IntoIterator<Item = Point<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let mut bounds = Bounds::new(points.next()?);
+        for point in points {
+            bounds.include(point);
+        }
+        Some(bounds)
+    }
+
+    /// Grows the bounds, if needed, to contain `point`
+    pub fn include(&mut self, point: Point<T>) {
+        self.min = Point::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    pub fn contains(&self, point: Point<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// The number of columns covered, counting both edges
+    #[track_caller]
+    pub fn width(&self) -> T
+    where
+        T: From<u8>,
+    {
+        overflow::add(overflow::sub(self.max.x, self.min.x), T::from(1))
+    }
+
+    /// The number of rows covered, counting both edges
+    #[track_caller]
+    pub fn height(&self) -> T
+    where
+        T: From<u8>,
+    {
+        overflow::add(overflow::sub(self.max.y, self.min.y), T::from(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let point = Point::new(2, 18);
+        let step: Vec2<i32> = Direction::Up.step();
+        assert_eq!(point + step, Point::new(2, 17));
+        assert_eq!(point - step * 3, Point::new(2, 21));
+        assert_eq!(Point::new(-2, 15) - point, Vec2::new(-4, -3));
+        assert_eq!(-Vec2::new(-4, 3), Vec2::new(4, -3));
+        assert_eq!(Vec2::new(-4, 0).signum(), Vec2::new(-1, 0));
+
+        let mut point = Point::new(0u8, 0);
+        point += Vec2::new(1, 2);
+        assert_eq!(point, Point::new(1, 2));
+    }
+
+    #[test]
+    fn distances() {
+        let sensor = Point::new(8, 7);
+        let beacon = Point::new(2, 10);
+        assert_eq!(sensor.manhattan_distance(beacon), 9);
+        assert_eq!(sensor.chebyshev_distance(beacon), 6);
+        assert_eq!((beacon - sensor).manhattan_length(), 9);
+        assert_eq!((beacon - sensor).chebyshev_length(), 6);
+        // Unsigned coordinates don't underflow
+        assert_eq!(
+            Point::new(1usize, 5).manhattan_distance(Point::new(3, 2)),
+            5
+        );
+    }
+
+    #[test]
+    fn directions() {
+        for direction in Direction::ALL {
+            let step: Vec2<i64> = direction.step();
+            assert_eq!(direction.opposite().step(), -step);
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(Direction8::from(direction).step(), step);
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        let steps: Vec<Vec2<i8>> = Direction8::ALL.iter().map(|d| d.step()).collect();
+        assert_eq!(
+            steps
+                .iter()
+                .filter(|step| step.x != 0 && step.y != 0)
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn bounding_box() {
+        let points = [(498, 4), (498, 6), (496, 6), (503, 4), (494, 9)];
+        let bounds = Bounds::of(points.map(Point::from)).unwrap();
+        assert_eq!(bounds.min, Point::new(494, 4));
+        assert_eq!(bounds.max, Point::new(503, 9));
+        assert_eq!((bounds.width(), bounds.height()), (10, 6));
+        assert!(bounds.contains(Point::new(500, 5)));
+        assert!(!bounds.contains(Point::new(500, 0)));
+        assert_eq!(Bounds::<i32>::of([]), None);
+
+        // Overflows are reported where the size was asked for
+        let bounds = Bounds::of([Point::new(0, i32::MIN), Point::new(0, i32::MAX)]).unwrap();
+        let line = line!() + 1;
+        let overflow = overflow::checked(|| bounds.height()).unwrap_err();
+        assert_eq!(overflow.location.file(), file!());
+        assert_eq!(overflow.location.line(), line);
+    }
+
+    #[test]
+    fn conversions() {
+        let point: Point<i64> = (3, -1).into();
+        assert_eq!(point.try_cast::<i16>(), Some(Point::new(3, -1)));
+        assert_eq!(point.try_cast::<usize>(), None);
+        assert_eq!(<(i64, i64)>::from(point), (3, -1));
+        assert_eq!(point.to_string(), "(3, -1)");
+    }
+}
@@ -0,0 +1,316 @@
+use std::ops::{Index, IndexMut};
+
+use crate::geometry::{Direction, Direction8, Point, Vec2};
+use crate::parse::{self, ParseError};
+
+/// A position in a [`Grid`]
+pub type Coord = Point<i64>;
+
+/// A rectangle of cells, stored row by row.
+///
+/// The top-left cell is at the grid's origin, which is `(0, 0)` unless the
+/// grid only covers part of a larger area. All positions are checked, so
+/// anything outside the grid is `None` rather than a panic or wrapping
+/// around to another row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    origin: Coord,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with every cell set to `value`
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; width * height],
+            width,
+            height,
+            origin: Point::new(0, 0),
+        }
+    }
+
+    /// Creates a grid from its cells, row by row. Panics if they don't fill
+    /// a whole number of rows.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(width * height, cells.len(), "cells don't fill every row");
+        Grid {
+            cells,
+            width,
+            height,
+            origin: Point::new(0, 0),
+        }
+    }
+
+    /// Parses character art, one row per line, converting each character
+    /// with `cell`. Rows must all be the same length and there must be at
+    /// least one. `expected` describes the characters `cell` accepts.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(Coord, char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut lines = parse::lines(input);
+        let mut height = 0;
+
+        for mut line in lines.by_ref() {
+            let start = cells.len();
+            while let Some(c) = line.peek_char() {
+                let position = Point::new((cells.len() - start) as i64, height as i64);
+                cells.push(cell(position, c).ok_or_else(|| line.error(expected))?);
+                line.next_char();
+            }
+            let row_width = cells.len() - start;
+            if height == 0 {
+                if row_width == 0 {
+                    return Err(line.error(expected));
+                }
+                width = row_width;
+            } else if row_width != width {
+                return Err(line.error_at(
+                    1,
+                    line.text(),
+                    format!("a row {width} characters wide"),
+                ));
+            }
+            height += 1;
+        }
+
+        if height == 0 {
+            return Err(lines.end_of_input(expected));
+        }
+        Ok(Grid {
+            cells,
+            width,
+            height,
+            origin: Point::new(0, 0),
+        })
+    }
+
+    /// Moves the grid so its top-left cell is at `origin`
+    pub fn with_origin(self, origin: Coord) -> Self {
+        Grid { origin, ..self }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The position of the top-left cell
+    pub fn origin(&self) -> Coord {
+        self.origin
+    }
+
+    fn index_of(&self, position: Coord) -> Option<usize> {
+        let col = usize::try_from(position.x.checked_sub(self.origin.x)?).ok()?;
+        let row = usize::try_from(position.y.checked_sub(self.origin.y)?).ok()?;
+        (col < self.width && row < self.height).then_some(row * self.width + col)
+    }
+
+    fn position_of(&self, index: usize) -> Coord {
+        self.origin + Vec2::new((index % self.width) as i64, (index / self.width) as i64)
+    }
+
+    pub fn contains(&self, position: Coord) -> bool {
+        self.index_of(position).is_some()
+    }
+
+    pub fn get(&self, position: Coord) -> Option<&T> {
+        self.index_of(position).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, position: Coord) -> Option<&mut T> {
+        self.index_of(position).map(|index| &mut self.cells[index])
+    }
+
+    /// Returns `false`, leaving the grid unchanged, if `position` is outside
+    /// the grid
+    pub fn set(&mut self, position: Coord, value: T) -> bool {
+        match self.get_mut(position) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Every position in the grid, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Coord> + '_ {
+        (0..self.cells.len()).map(|index| self.position_of(index))
+    }
+
+    /// Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The up to four orthogonally adjacent positions
+    pub fn neighbours4(&self, position: Coord) -> impl Iterator<Item = Coord> + '_ {
+        Direction::ALL
+            .into_iter()
+            .map(move |direction| position + direction.step())
+            .filter(|&adjacent| self.contains(adjacent))
+    }
+
+    /// The up to eight surrounding positions, including diagonals
+    pub fn neighbours8(&self, position: Coord) -> impl Iterator<Item = Coord> + '_ {
+        Direction8::ALL
+            .into_iter()
+            .map(move |direction| position + direction.step())
+            .filter(|&adjacent| self.contains(adjacent))
+    }
+
+    /// The cells in a straight line from `position`, not including
+    /// `position` itself, up to the edge of the grid
+    pub fn ray(&self, position: Coord, step: Vec2<i64>) -> impl Iterator<Item = (Coord, &T)> {
+        assert!(step != Vec2::default(), "a ray needs to move");
+        std::iter::successors(Some(position), move |&position| Some(position + step))
+            .skip(1)
+            .map_while(|position| Some((position, self.get(position)?)))
+    }
+
+    /// The cells in row `y`, if it's in the grid
+    pub fn row(&self, y: i64) -> Option<&[T]> {
+        let start = self.index_of(Point::new(self.origin.x, y))?;
+        Some(&self.cells[start..start + self.width])
+    }
+
+    /// Every row, top to bottom
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells in column `x` from top to bottom, empty if it isn't in the
+    /// grid
+    pub fn column(&self, x: i64) -> impl DoubleEndedIterator<Item = &T> {
+        let start = self.index_of(Point::new(x, self.origin.y));
+        let cells = match start {
+            Some(start) => &self.cells[start..],
+            None => &[],
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    /// Draws the grid as text, one line per row
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                text.push('\n');
+            }
+            text.extend(row.iter().map(&mut cell));
+        }
+        text
+    }
+}
+
+/// Panics if the position is outside the grid
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Coord) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{position} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, position: Coord) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{position} is outside the grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<char> {
+        Grid::parse("abc\ndef", "a letter", |_, c| Some(c)).unwrap()
+    }
+
+    #[test]
+    fn checked_access() {
+        let mut grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 2)), None);
+        assert!(grid.set(Point::new(1, 1), 'E'));
+        assert!(!grid.set(Point::new(1, 2), 'x'));
+        assert_eq!(grid.render(|&c| c), "abc\ndEf");
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = example();
+        let corner: Vec<_> = grid.neighbours4(Point::new(0, 0)).collect();
+        assert_eq!(corner, [Point::new(0, 1), Point::new(1, 0)]);
+        let middle: Vec<_> = grid.neighbours8(Point::new(1, 0)).collect();
+        assert_eq!(
+            middle,
+            [(0, 0), (2, 0), (0, 1), (1, 1), (2, 1)].map(Point::from)
+        );
+    }
+
+    #[test]
+    fn rows_columns_and_rays() {
+        let grid = example();
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(grid.column(2).rev().collect::<String>(), "fc");
+        assert_eq!(grid.column(3).count(), 0);
+        let ray: String = grid
+            .ray(Point::new(0, 1), Vec2::new(1, 0))
+            .map(|(_, &c)| c)
+            .collect();
+        assert_eq!(ray, "ef");
+        let ray: Vec<_> = grid.ray(Point::new(0, 0), Vec2::new(1, 1)).collect();
+        assert_eq!(ray, [(Point::new(1, 1), &'e')]);
+    }
+
+    #[test]
+    fn offset_origin() {
+        let mut grid = Grid::new(3, 2, '.').with_origin(Point::new(494, 0));
+        assert!(grid.set(Point::new(496, 1), '#'));
+        assert_eq!(grid.get(Point::new(493, 0)), None);
+        assert_eq!(grid[Point::new(496, 1)], '#');
+        assert_eq!(grid.positions().last(), Some(Point::new(496, 1)));
+        assert_eq!(grid.row(1), Some(&['.', '.', '#'][..]));
+        assert_eq!(grid.column(496).collect::<String>(), ".#");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let digits = |_, c: char| c.to_digit(10);
+        let error = Grid::parse("123\n45x", "a digit", digits).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected a digit, found `x`"
+        );
+        let error = Grid::parse("123\n45", "a digit", digits).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected a row 3 characters wide, found `45`"
+        );
+        let error = Grid::parse("", "a digit", digits).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: expected a digit, found end of line"
+        );
+    }
+}
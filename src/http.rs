@@ -0,0 +1,388 @@
+//! Just enough HTTP/1.1 for `aoc serve`, which solves puzzle inputs sent
+//! to it: `POST /day/{n}` with the input as the body answers with the same
+//! JSON as `aoc run --format json`.
+//!
+//! Each connection carries a single request, which must give its body's
+//! length in a `Content-Length` header. The body can be at most
+//! [`Limits::max_body`] bytes, and every part of the day gets
+//! [`Limits::timeout`] to solve it in, as does reading the whole request.
+//!
+//! ```text
+//! $ curl --data-binary @input.txt localhost:8080/day/6
+//! {"day":6,"parse_ns":1143,"parts":[{"part":1,"answer":1802,...}],"error":null}
+//! ```
+
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
+
+use crate::json::Json;
+use crate::runner::{self, Settings};
+use crate::{get_day, Day, Part};
+
+/// The longest a request line and its headers can be together
+const MAX_HEAD_BYTES: u64 = 8 * 1024;
+
+/// What a single request is allowed to use
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+    /// The longest input that's accepted, in bytes
+    pub max_body: usize,
+    /// How long reading the request, writing the response and solving each
+    /// part can take
+    pub timeout: Duration,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_body: 1024 * 1024,
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Json,
+}
+
+impl Response {
+    /// A response with a body like `{"error":"..."}`
+    pub fn error(status: u16, message: impl Into<String>) -> Self {
+        Response {
+            status,
+            body: Json::object().with("error", message.into()),
+        }
+    }
+
+    pub fn write_to(&self, out: &mut impl Write) -> io::Result<()> {
+        let body = format!("{}\n", self.body);
+        write!(out, "HTTP/1.1 {} {}\r\n", self.status, reason(self.status))?;
+        if self.status == 405 {
+            // The only thing that can be done to a day is posting to it
+            write!(out, "Allow: POST\r\n")?;
+        }
+        write!(
+            out,
+            "Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )?;
+        out.flush()
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        411 => "Length Required",
+        413 => "Content Too Large",
+        422 => "Unprocessable Content",
+        431 => "Request Header Fields Too Large",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
+
+/// Reads a request, refusing bodies longer than `max_body` before reading
+/// them. The error is the response to send back instead of handling it.
+pub fn read_request(reader: &mut impl BufRead, max_body: usize) -> Result<Request, Response> {
+    let mut head = reader.by_ref().take(MAX_HEAD_BYTES);
+    let mut line = String::new();
+    read_line(&mut head, &mut line)?;
+    let mut words = line.split_whitespace();
+    let (Some(method), Some(path), Some(version), None) =
+        (words.next(), words.next(), words.next(), words.next())
+    else {
+        return Err(Response::error(400, "malformed request line"));
+    };
+    if !version.starts_with("HTTP/1.") {
+        return Err(Response::error(
+            400,
+            format!("unsupported version `{version}`"),
+        ));
+    }
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut content_length = None;
+    loop {
+        line.clear();
+        read_line(&mut head, &mut line)?;
+        if line.trim_end().is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(Response::error(400, "malformed header"));
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = Some(value.parse::<u64>().map_err(|_| {
+                Response::error(
+                    400,
+                    format!("Content-Length must be a number, not `{value}`"),
+                )
+            })?);
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(Response::error(411, "chunked bodies aren't supported"));
+        }
+    }
+
+    let length = match content_length {
+        Some(length) => length,
+        None if method == "POST" => {
+            return Err(Response::error(411, "the Content-Length header is missing"))
+        }
+        None => 0,
+    };
+    if length > max_body as u64 {
+        return Err(Response::error(
+            413,
+            format!("the input can be at most {max_body} bytes, not {length}"),
+        ));
+    }
+    let mut body = vec![0; length as usize];
+    reader
+        .read_exact(&mut body)
+        .map_err(|err| match err.kind() {
+            ErrorKind::UnexpectedEof => {
+                Response::error(400, "the body is shorter than Content-Length")
+            }
+            _ => io_error(err),
+        })?;
+    let body =
+        String::from_utf8(body).map_err(|_| Response::error(400, "the input isn't UTF-8"))?;
+    Ok(Request { method, path, body })
+}
+
+/// Reads one line of the request's head into `line`
+fn read_line(head: &mut io::Take<&mut impl BufRead>, line: &mut String) -> Result<(), Response> {
+    match head.read_line(line) {
+        Ok(_) if line.ends_with('\n') => Ok(()),
+        Ok(_) if head.limit() == 0 => Err(Response::error(431, "the headers are too long")),
+        Ok(_) => Err(Response::error(400, "the request ended in its headers")),
+        Err(err) => Err(io_error(err)),
+    }
+}
+
+fn io_error(err: io::Error) -> Response {
+    match err.kind() {
+        ErrorKind::WouldBlock | ErrorKind::TimedOut => {
+            Response::error(408, "timed out reading the request")
+        }
+        ErrorKind::InvalidData => Response::error(400, "the headers aren't UTF-8"),
+        _ => Response::error(400, format!("could not read the request: {err}")),
+    }
+}
+
+/// Solves the day in `request`'s path for the input in its body. A day
+/// that panicked is a 500, one whose input can't be parsed or has no answer
+/// for a part is a 422, and one with a part that timed out is a 503, all
+/// with the day's result as their body.
+pub fn handle(request: &Request, limits: &Limits) -> Response {
+    let Some(number) = request.path.strip_prefix("/day/") else {
+        return Response::error(404, format!("no such path `{}`", request.path));
+    };
+    let Some(day) = number.parse().ok().and_then(get_day) else {
+        return Response::error(404, format!("there is no solution for day `{number}`"));
+    };
+    if request.method != "POST" {
+        return Response::error(405, "post the input to solve the day");
+    }
+    solve(day, &request.body, limits)
+}
+
+/// Solves `day` for `input`, answering as [`handle`] does
+fn solve(day: &'static Day, input: &str, limits: &Limits) -> Response {
+    let settings = Settings {
+        // Solve both parts at once, so the request takes about one timeout
+        threads: Part::ALL.len(),
+        timeout: Some(limits.timeout),
+        ..Settings::default()
+    };
+    let result = runner::run_day(day, input, &settings);
+    let status = if result.panicked {
+        500
+    } else if result.error.is_some() || result.parts.iter().any(|part| part.error.is_some()) {
+        422
+    } else if result.parts.iter().any(|part| part.timed_out) {
+        503
+    } else {
+        200
+    };
+    Response {
+        status,
+        body: result.to_json(),
+    }
+}
+
+/// Reads from a connection until a deadline, however slowly the client
+/// sends what it's sending
+struct Deadline<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for Deadline<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(ErrorKind::TimedOut.into());
+        }
+        self.stream.set_read_timeout(Some(left))?;
+        self.stream.read(buf)
+    }
+}
+
+/// Answers the request on `stream`, returning a line for the server's log
+/// like `POST /day/6 200`
+pub fn respond(stream: &TcpStream, limits: &Limits) -> io::Result<String> {
+    stream.set_write_timeout(Some(limits.timeout))?;
+    let mut reader = BufReader::new(Deadline {
+        stream,
+        deadline: Instant::now() + limits.timeout,
+    });
+    let (summary, response) = match read_request(&mut reader, limits.max_body) {
+        Ok(request) => {
+            let response = handle(&request, limits);
+            (format!("{} {}", request.method, request.path), response)
+        }
+        Err(response) => ("(bad request)".to_string(), response),
+    };
+    response.write_to(&mut &*stream)?;
+    Ok(format!("{summary} {}", response.status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn post(path: &str, body: &str) -> Request {
+        Request {
+            method: "POST".to_string(),
+            path: path.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn reading_requests() {
+        let text = "POST /day/6 HTTP/1.1\r\nHost: localhost\r\ncontent-length: 5\r\n\r\nabcdefgh";
+        assert_eq!(
+            read_request(&mut text.as_bytes(), 100),
+            Ok(post("/day/6", "abcde"))
+        );
+
+        let status = |text: &str| read_request(&mut text.as_bytes(), 4).unwrap_err().status;
+        assert_eq!(status("POST /day/6 HTTP/1.1\r\n\r\n"), 411);
+        assert_eq!(
+            status("POST /day/6 HTTP/1.1\r\nContent-Length: 5\r\n\r\nabcde"),
+            413
+        );
+        assert_eq!(
+            status("POST /day/6 HTTP/1.1\r\nContent-Length: 3\r\n\r\nab"),
+            400
+        );
+        assert_eq!(status("POST /day/6\r\n\r\n"), 400);
+        assert_eq!(status("POST /day/6 HTTP/1.1\r\nHost"), 400);
+        let long_header = format!("GET / HTTP/1.1\r\nX: {}\r\n\r\n", "x".repeat(10_000));
+        assert_eq!(status(&long_header), 431);
+    }
+
+    #[test]
+    fn solving_days() {
+        let limits = Limits::default();
+        let response = handle(&post("/day/1", examples::input("day1")), &limits);
+        assert_eq!(response.status, 200);
+        let parts = response.body.get("parts").unwrap().as_array().unwrap();
+        assert_eq!(parts[0].get("answer").unwrap().as_i64(), Some(24000));
+        assert_eq!(parts[1].get("answer").unwrap().as_i64(), Some(45000));
+
+        assert_eq!(handle(&post("/day/1", "one\n"), &limits).status, 422);
+        // Rejected before the cave is allocated, rather than aborting
+        let huge_cave = post("/day/14", "500,0 -> 500,100000000000");
+        assert_eq!(handle(&huge_cave, &limits).status, 422);
+        let response = solve(&crate::tests::PANICKING, "", &limits);
+        assert_eq!(response.status, 500);
+        assert_eq!(response.body.get("panicked"), Some(&Json::Bool(true)));
+        assert_eq!(handle(&post("/day/99", ""), &limits).status, 404);
+        assert_eq!(handle(&post("/days", ""), &limits).status, 404);
+        let get = Request {
+            method: "GET".to_string(),
+            ..post("/day/1", "")
+        };
+        assert_eq!(handle(&get, &limits).status, 405);
+    }
+
+    #[test]
+    fn over_a_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            respond(&stream, &Limits::default()).unwrap()
+        });
+
+        let mut client = TcpStream::connect(address).unwrap();
+        let input = examples::input("day6");
+        write!(
+            client,
+            "POST /day/6 HTTP/1.1\r\nContent-Length: {}\r\n\r\n{input}",
+            input.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+
+        assert_eq!(server.join().unwrap(), "POST /day/6 200");
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        assert!(head.starts_with("HTTP/1.1 200 OK\r\n"));
+        let body = Json::parse(body).unwrap();
+        let parts = body.get("parts").unwrap().as_array().unwrap();
+        assert_eq!(parts[0].get("answer").unwrap().as_i64(), Some(7));
+    }
+
+    #[test]
+    fn slow_client() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let limits = Limits {
+            timeout: Duration::from_millis(300),
+            ..Limits::default()
+        };
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let start = Instant::now();
+            (respond(&stream, &limits).unwrap(), start.elapsed())
+        });
+
+        // Each byte comes well within the timeout, but the request as a
+        // whole takes much longer
+        let mut client = TcpStream::connect(address).unwrap();
+        for byte in b"POST /day/6 HTTP/1.1\r\nContent-Length: 1\r\n\r\nx" {
+            if client.write_all(&[*byte]).is_err() || server.is_finished() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+
+        let (summary, elapsed) = server.join().unwrap();
+        assert_eq!(summary, "(bad request) 408");
+        assert!(elapsed < Duration::from_secs(1), "took {elapsed:?}");
+    }
+}
@@ -0,0 +1,135 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::PathBuf;
+
+use crate::Day;
+
+/// Where to read a day's puzzle input from
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The `dayN.txt` checked into the repo
+    #[default]
+    Bundled,
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Parses the value of `--input`, where `-` means stdin
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    pub fn load(&self, day: &Day) -> Result<Cow<'static, str>, InputError> {
+        match self {
+            InputSource::Bundled => Ok(Cow::Borrowed(day.input)),
+            InputSource::File(path) => {
+                fs::read_to_string(path)
+                    .map(Cow::Owned)
+                    .map_err(|error| InputError {
+                        source: self.clone(),
+                        error,
+                    })
+            }
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|error| InputError {
+                        source: self.clone(),
+                        error,
+                    })?;
+                Ok(Cow::Owned(input))
+            }
+        }
+    }
+
+    /// Opens the input to be read a bit at a time, for
+    /// [`runner::stream_day`](crate::runner::stream_day)
+    pub fn open(&self, day: &Day) -> Result<Box<dyn BufRead>, InputError> {
+        match self {
+            InputSource::Bundled => Ok(Box::new(day.input.as_bytes())),
+            InputSource::File(path) => match fs::File::open(path) {
+                Ok(file) => Ok(Box::new(BufReader::new(file))),
+                Err(error) => Err(InputError {
+                    source: self.clone(),
+                    error,
+                }),
+            },
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::Bundled => write!(f, "bundled input"),
+            InputSource::File(path) => write!(f, "`{}`", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    pub source: InputSource,
+    pub error: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "could not read input from {}: ", self.source)?;
+        match self.error.kind() {
+            io::ErrorKind::NotFound => write!(f, "no such file"),
+            io::ErrorKind::PermissionDenied => write!(f, "permission denied"),
+            io::ErrorKind::InvalidData => write!(f, "input is not valid UTF-8"),
+            _ => write!(f, "{}", self.error),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_day;
+
+    #[test]
+    fn parsing_input_arg() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("day1.txt"),
+            InputSource::File(PathBuf::from("day1.txt"))
+        );
+    }
+
+    #[test]
+    fn falls_back_to_bundled_input() {
+        let day = get_day(1).unwrap();
+        assert_eq!(InputSource::Bundled.load(day).unwrap(), day.input);
+    }
+
+    #[test]
+    fn missing_file() {
+        let day = get_day(1).unwrap();
+        let error = InputSource::from_arg("does/not/exist.txt")
+            .load(day)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "could not read input from `does/not/exist.txt`: no such file"
+        );
+    }
+}
@@ -0,0 +1,404 @@
+use std::fmt::{self, Write};
+
+use crate::parse::{Line, ParseError};
+
+/// A JSON value, just enough to save and load results without pulling in
+/// a serialization library
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    /// Integers are kept separate from floats so large answers round-trip
+    /// exactly
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<Json>),
+    /// Keys keep the order they were inserted or parsed in
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Starts an empty object, to be filled in with [`Json::with`]
+    pub fn object() -> Self {
+        Json::Object(Vec::new())
+    }
+
+    /// Adds a key to an object
+    pub fn with(mut self, key: &str, value: impl Into<Json>) -> Self {
+        if let Json::Object(entries) = &mut self {
+            entries.push((key.to_string(), value.into()));
+        }
+        self
+    }
+
+    /// Looks up a key, if this is an object
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => entries
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Json::Integer(number) => Some(number),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Json::Integer(number) => Some(number as f64),
+            Json::Float(number) => Some(number),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Parses a single JSON document
+    pub fn parse(text: &str) -> Result<Json, ParseError> {
+        // The parser works on one line, so a document spread over several
+        // lines is joined first. Newlines can only appear between tokens, so
+        // this doesn't change its meaning, but error columns then count
+        // from the start of the document.
+        let joined = text.lines().collect::<Vec<_>>().join(" ");
+        let mut line = Line::new(1, &joined);
+        let value = parse_value(&mut line)?;
+        skip_whitespace(&mut line);
+        line.finish()?;
+        Ok(value)
+    }
+
+    /// Formats the value with two-space indentation
+    pub fn pretty(&self) -> String {
+        let mut output = String::new();
+        self.write_pretty(&mut output, 0);
+        output
+    }
+
+    fn write_pretty(&self, output: &mut String, indent: usize) {
+        let (open, close, entries): (_, _, Vec<(Option<&str>, &Json)>) = match self {
+            Json::Array(values) if !values.is_empty() => {
+                ('[', ']', values.iter().map(|value| (None, value)).collect())
+            }
+            Json::Object(entries) if !entries.is_empty() => (
+                '{',
+                '}',
+                entries
+                    .iter()
+                    .map(|(key, value)| (Some(key.as_str()), value))
+                    .collect(),
+            ),
+            _ => {
+                write!(output, "{self}").unwrap();
+                return;
+            }
+        };
+
+        output.push(open);
+        for (index, (key, value)) in entries.iter().enumerate() {
+            if index > 0 {
+                output.push(',');
+            }
+            output.push('\n');
+            output.extend(std::iter::repeat_n(' ', indent + 2));
+            if let Some(key) = key {
+                write_string(output, key).unwrap();
+                output.push_str(": ");
+            }
+            value.write_pretty(output, indent + 2);
+        }
+        output.push('\n');
+        output.extend(std::iter::repeat_n(' ', indent));
+        output.push(close);
+    }
+}
+
+/// Formats the value on a single line
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{value}"),
+            Json::Integer(number) => write!(f, "{number}"),
+            // JSON has no infinities or NaN
+            Json::Float(number) if !number.is_finite() => write!(f, "null"),
+            Json::Float(number) if number.fract() == 0.0 => write!(f, "{number:.1}"),
+            Json::Float(number) => write!(f, "{number}"),
+            Json::String(string) => write_string(f, string),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(entries) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(output: &mut impl Write, string: &str) -> fmt::Result {
+    output.write_char('"')?;
+    for c in string.chars() {
+        match c {
+            '"' => output.write_str("\\\"")?,
+            '\\' => output.write_str("\\\\")?,
+            '\n' => output.write_str("\\n")?,
+            '\r' => output.write_str("\\r")?,
+            '\t' => output.write_str("\\t")?,
+            c if c.is_control() => write!(output, "\\u{:04x}", c as u32)?,
+            c => output.write_char(c)?,
+        }
+    }
+    output.write_char('"')
+}
+
+fn skip_whitespace(line: &mut Line) {
+    line.take_while(char::is_whitespace);
+}
+
+fn parse_value(line: &mut Line) -> Result<Json, ParseError> {
+    skip_whitespace(line);
+    match line.peek_char() {
+        Some('{') => {
+            line.expect("{")?;
+            let mut entries = Vec::new();
+            skip_whitespace(line);
+            if line.eat("}") {
+                return Ok(Json::Object(entries));
+            }
+            loop {
+                skip_whitespace(line);
+                if line.peek_char() != Some('"') {
+                    return Err(line.error("a string key"));
+                }
+                let key = parse_string(line)?;
+                skip_whitespace(line);
+                line.expect(":")?;
+                entries.push((key, parse_value(line)?));
+                skip_whitespace(line);
+                if line.eat("}") {
+                    return Ok(Json::Object(entries));
+                }
+                if !line.eat(",") {
+                    return Err(line.error("`,` or `}`"));
+                }
+            }
+        }
+        Some('[') => {
+            line.expect("[")?;
+            let mut values = Vec::new();
+            skip_whitespace(line);
+            if line.eat("]") {
+                return Ok(Json::Array(values));
+            }
+            loop {
+                values.push(parse_value(line)?);
+                skip_whitespace(line);
+                if line.eat("]") {
+                    return Ok(Json::Array(values));
+                }
+                if !line.eat(",") {
+                    return Err(line.error("`,` or `]`"));
+                }
+            }
+        }
+        Some('"') => parse_string(line).map(Json::String),
+        Some('-' | '0'..='9') => parse_number(line),
+        _ if line.eat("null") => Ok(Json::Null),
+        _ if line.eat("true") => Ok(Json::Bool(true)),
+        _ if line.eat("false") => Ok(Json::Bool(false)),
+        _ => Err(line.error("a JSON value")),
+    }
+}
+
+fn parse_string(line: &mut Line) -> Result<String, ParseError> {
+    line.expect("\"")?;
+    let mut string = String::new();
+    loop {
+        match line.next_char() {
+            Some('"') => return Ok(string),
+            Some('\\') => {
+                let escape = line.one_of(&[
+                    ('"', '"'),
+                    ('\\', '\\'),
+                    ('/', '/'),
+                    ('b', '\u{8}'),
+                    ('f', '\u{c}'),
+                    ('n', '\n'),
+                    ('r', '\r'),
+                    ('t', '\t'),
+                    ('u', 'u'),
+                ])?;
+                if escape == 'u' {
+                    let column = line.column();
+                    let hex = line.take_while(|c| c.is_ascii_hexdigit());
+                    let c = (hex.len() == 4)
+                        .then(|| u32::from_str_radix(hex, 16).ok())
+                        .flatten()
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| line.error_at(column, hex, "four hex digits"))?;
+                    string.push(c);
+                } else {
+                    string.push(escape);
+                }
+            }
+            Some(c) => string.push(c),
+            None => return Err(line.error("the closing `\"`")),
+        }
+    }
+}
+
+fn parse_number(line: &mut Line) -> Result<Json, ParseError> {
+    let column = line.column();
+    let text = line.take_while(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'));
+    let number = if text.contains(['.', 'e', 'E']) {
+        text.parse().ok().map(Json::Float)
+    } else {
+        text.parse().ok().map(Json::Integer)
+    };
+    number.ok_or_else(|| line.error_at(column, text, "a number"))
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Bool(value)
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Json {
+                fn from(number: $t) -> Self {
+                    Json::Integer(i64::try_from(number).expect("number doesn't fit in an i64"))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, i16, i32, u32, i64, u64, usize);
+
+impl From<f64> for Json {
+    fn from(number: f64) -> Self {
+        Json::Float(number)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(string: &str) -> Self {
+        Json::String(string.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(string: String) -> Self {
+        Json::String(string)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(values: Vec<T>) -> Self {
+        Json::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let value = Json::object()
+            .with("day", 11)
+            .with("answer", 25590400731u64)
+            .with("text", "a \"quoted\"\nline")
+            .with("median", 1.5)
+            .with("error", None::<String>)
+            .with("runs", vec![1, 2, 3])
+            .with("empty", Json::object());
+        let compact = value.to_string();
+        assert_eq!(
+            compact,
+            r#"{"day":11,"answer":25590400731,"text":"a \"quoted\"\nline","median":1.5,"error":null,"runs":[1,2,3],"empty":{}}"#
+        );
+        assert_eq!(Json::parse(&compact).unwrap(), value);
+        assert_eq!(Json::parse(&value.pretty()).unwrap(), value);
+    }
+
+    #[test]
+    fn pretty_printing() {
+        let value = Json::object()
+            .with("results", vec![Json::object().with("day", 1)])
+            .with("ok", true);
+        assert_eq!(
+            value.pretty(),
+            "{\n  \"results\": [\n    {\n      \"day\": 1\n    }\n  ],\n  \"ok\": true\n}"
+        );
+    }
+
+    #[test]
+    fn unicode_escapes() {
+        let value = Json::parse(r#"["é\t", 2e3, -4]"#).unwrap();
+        assert_eq!(
+            value,
+            Json::Array(vec![
+                Json::String("é\t".to_string()),
+                Json::Float(2000.0),
+                Json::Integer(-4)
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Json::parse(r#"{"day": 1,}"#).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 11: expected a string key, found `}`"
+        );
+        let error = Json::parse("[1 2]").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 4: expected `,` or `]`, found `2`"
+        );
+    }
+}
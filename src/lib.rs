@@ -0,0 +1,361 @@
+pub mod allocations;
+pub mod answers;
+pub mod bench;
+pub mod cancel;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod differential;
+pub mod examples;
+pub mod explore;
+pub mod generate;
+pub mod geometry;
+pub mod grid;
+pub mod http;
+pub mod input;
+pub mod json;
+pub mod log;
+pub mod overflow;
+pub mod params;
+pub mod parse;
+pub mod pool;
+pub mod progress;
+pub mod random;
+pub mod runner;
+pub mod snapshot;
+pub mod stream;
+pub mod toml;
+pub mod trace;
+
+mod solution;
+
+pub use parse::ParseError;
+pub use solution::{Answer, Context, Solution};
+
+use std::any::Any;
+use std::fmt;
+use std::io::BufRead;
+
+use explore::{Explore, Query};
+use stream::{StreamError, Streaming};
+
+/// One day's puzzle, with its [`Solution`] erased so every day can be run
+/// through the same interface
+pub struct Day {
+    pub number: u8,
+    pub input: &'static str,
+    /// See [`Solution::PARAMS`]
+    pub params: &'static [params::Param],
+    parse: fn(&str) -> Result<Parsed, ParseError>,
+    part1: fn(&Parsed, &Context) -> Result<Answer, String>,
+    part2: fn(&Parsed, &Context) -> Option<Result<Answer, String>>,
+    /// Only days that implement [`Streaming`] can be streamed
+    stream: Option<StreamFn>,
+    /// What can be asked about the parsed input, empty unless the day
+    /// implements [`Explore`]
+    pub queries: &'static [Query],
+    session: fn() -> Session,
+    query: Option<QueryFn>,
+    complete: Option<CompleteFn>,
+}
+
+type StreamFn =
+    fn(&mut dyn BufRead, &[Part], &Context) -> Result<Vec<Result<Answer, String>>, StreamError>;
+type QueryFn = fn(&Parsed, &mut Session, &str, &[&str]) -> Result<String, String>;
+type CompleteFn = fn(&Parsed, &str, usize, &str) -> Vec<String>;
+
+/// A day's input after it has been parsed by [`Solution::parse`]
+pub struct Parsed(Box<dyn Any + Send + Sync>);
+
+/// What's kept between queries about a day's parsed input, see
+/// [`Explore::Session`]
+pub struct Session(Box<dyn Any>);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl Day {
+    const fn new<S: Solution>(number: u8, input: &'static str) -> Self {
+        Day {
+            number,
+            input,
+            params: S::PARAMS,
+            parse: |input| S::parse(input).map(|parsed| Parsed(Box::new(parsed))),
+            part1: |parsed, context| S::part1(parsed.downcast::<S>(), context),
+            part2: |parsed, context| S::part2(parsed.downcast::<S>(), context),
+            stream: None,
+            queries: &[],
+            session: || Session(Box::new(())),
+            query: None,
+            complete: None,
+        }
+    }
+
+    /// Lets the day be solved with [`Day::stream`] through `S`, which must
+    /// be the day's own solution
+    const fn streaming<S: Streaming>(self) -> Self {
+        Day {
+            stream: Some(S::stream),
+            ..self
+        }
+    }
+
+    /// Lets the day's parsed input be queried with [`Day::query`] through
+    /// `S`, which must be the day's own solution
+    const fn explorable<S: Explore>(self) -> Self {
+        Day {
+            queries: S::QUERIES,
+            session: || Session(Box::new(S::Session::default())),
+            query: Some(|parsed, session, name, args| {
+                S::query(parsed.downcast::<S>(), session.downcast::<S>(), name, args)
+            }),
+            complete: Some(|parsed, name, index, prefix| {
+                S::complete(parsed.downcast::<S>(), name, index, prefix)
+            }),
+            ..self
+        }
+    }
+
+    /// Parses `input` once it's been [normalized](parse::normalize)
+    pub fn parse(&self, input: &str) -> Result<Parsed, ParseError> {
+        (self.parse)(&parse::normalize(input)).map_err(|error| ParseError {
+            day: Some(self.number),
+            ..error
+        })
+    }
+
+    pub fn can_stream(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Solves `parts` while reading `input`, without parsing all of it
+    /// first. The input is normalized a line at a time, like
+    /// [`parse::normalize`] does. Returns `None` if this day can't be
+    /// streamed.
+    pub fn stream(
+        &self,
+        input: &mut dyn BufRead,
+        parts: &[Part],
+        context: &Context,
+    ) -> Option<Result<Vec<Result<Answer, String>>, StreamError>> {
+        let stream = self.stream?;
+        let answers = stream::skip_bom(input)
+            .map_err(StreamError::from)
+            .and_then(|()| stream(input, parts, context));
+        Some(answers.map_err(|error| match error {
+            StreamError::Parse(error) => StreamError::Parse(ParseError {
+                day: Some(self.number),
+                ..error
+            }),
+            error => error,
+        }))
+    }
+
+    /// Starts a session of queries, for [`Day::query`] to keep what it
+    /// works out between them
+    pub fn session(&self) -> Session {
+        (self.session)()
+    }
+
+    /// Answers the query called `name` about `parsed`, which must be this
+    /// day's input, as is `session`. The error says what's wrong with the
+    /// query.
+    pub fn query(
+        &self,
+        parsed: &Parsed,
+        session: &mut Session,
+        name: &str,
+        args: &[&str],
+    ) -> Result<String, String> {
+        let (Some(query), Some(answer)) = (
+            self.queries.iter().find(|query| query.name == name),
+            self.query,
+        ) else {
+            return Err(format!("day {} has no query `{name}`", self.number));
+        };
+        if args.len() != query.args.len() {
+            return Err(format!("usage: {}", query.usage()));
+        }
+        answer(parsed, session, name, args)
+    }
+
+    /// The values that start with `prefix` for argument `index` of the query
+    /// called `name` in order, which are empty if there's no such argument
+    pub fn complete(&self, parsed: &Parsed, name: &str, index: usize, prefix: &str) -> Vec<String> {
+        let takes_argument = self
+            .queries
+            .iter()
+            .any(|query| query.name == name && index < query.args.len());
+        match self.complete {
+            Some(complete) if takes_argument => {
+                let mut completions = complete(parsed, name, index, prefix);
+                completions.sort();
+                completions
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns `None` if this part hasn't been solved yet, and an error if
+    /// the input has no answer for it
+    pub fn solve(
+        &self,
+        part: Part,
+        parsed: &Parsed,
+        context: &Context,
+    ) -> Option<Result<Answer, String>> {
+        match part {
+            Part::One => Some((self.part1)(parsed, context)),
+            Part::Two => (self.part2)(parsed, context),
+        }
+    }
+}
+
+impl Parsed {
+    fn downcast<S: Solution>(&self) -> &S::Parsed {
+        self.0
+            .downcast_ref()
+            .expect("parsed input belongs to a different day")
+    }
+}
+
+impl Session {
+    fn downcast<S: Explore>(&mut self) -> &mut S::Session {
+        self.0
+            .downcast_mut()
+            .expect("session belongs to a different day")
+    }
+}
+
+pub static DAYS: [Day; 15] = [
+    Day::new::<day1::Day1>(1, day1::INPUT).streaming::<day1::Day1>(),
+    Day::new::<day2::Day2>(2, day2::INPUT).streaming::<day2::Day2>(),
+    Day::new::<day3::Day3>(3, day3::INPUT).streaming::<day3::Day3>(),
+    Day::new::<day4::Day4>(4, day4::INPUT).streaming::<day4::Day4>(),
+    Day::new::<day5::Day5>(5, day5::INPUT),
+    Day::new::<day6::Day6>(6, day6::INPUT).streaming::<day6::Day6>(),
+    Day::new::<day7::Day7>(7, day7::INPUT).explorable::<day7::Day7>(),
+    Day::new::<day8::Day8>(8, day8::INPUT),
+    Day::new::<day9::Day9>(9, day9::INPUT).streaming::<day9::Day9>(),
+    Day::new::<day10::Day10>(10, day10::INPUT).streaming::<day10::Day10>(),
+    Day::new::<day11::Day11>(11, day11::INPUT),
+    Day::new::<day12::Day12>(12, day12::INPUT).explorable::<day12::Day12>(),
+    Day::new::<day13::Day13>(13, day13::INPUT),
+    Day::new::<day14::Day14>(14, day14::INPUT),
+    Day::new::<day15::Day15>(15, day15::INPUT).explorable::<day15::Day15>(),
+];
+
+pub fn get_day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Stands in for a solution with a bug, whose first part answers and
+    /// whose second part panics
+    pub(crate) struct Panicking;
+
+    impl Solution for Panicking {
+        type Parsed = ();
+
+        fn parse(_input: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part1(_parsed: &(), _context: &Context) -> Result<Answer, String> {
+            Ok(Answer::Number(1))
+        }
+
+        fn part2(_parsed: &(), _context: &Context) -> Option<Result<Answer, String>> {
+            panic!("a bug in part 2")
+        }
+    }
+
+    pub(crate) static PANICKING: Day = Day::new::<Panicking>(0, "");
+
+    #[test]
+    fn days_are_in_order() {
+        for (index, day) in DAYS.iter().enumerate() {
+            assert_eq!(day.number as usize, index + 1);
+        }
+    }
+
+    #[test]
+    fn solving_through_erased_day() {
+        let day = get_day(6).unwrap();
+        let parsed = day.parse("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap();
+        let context = Context::with_defaults(day);
+        assert_eq!(
+            day.solve(Part::One, &parsed, &context),
+            Some(Ok(Answer::Number(7)))
+        );
+        assert_eq!(
+            day.solve(Part::Two, &parsed, &context),
+            Some(Ok(Answer::Number(19)))
+        );
+    }
+
+    #[test]
+    fn solving_with_params() {
+        let day = get_day(6).unwrap();
+        let parsed = day.parse("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap();
+        let mut params = params::Params::new();
+        params.set(6, "packet_marker", 14).unwrap();
+        let context = Context::new(day, &params);
+        assert_eq!(
+            day.solve(Part::One, &parsed, &context),
+            Some(Ok(Answer::Number(19)))
+        );
+    }
+
+    #[test]
+    fn parse_errors_include_the_day() {
+        let error = get_day(2).unwrap().parse("A Y\nB Q").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "day 2, line 2, column 3: expected `X`, `Y` or `Z`, found `Q`"
+        );
+    }
+
+    #[test]
+    fn unsolved_part() {
+        let day = get_day(14).unwrap();
+        let parsed = day.parse("498,4 -> 498,6 -> 496,6").unwrap();
+        let context = Context::with_defaults(day);
+        assert_eq!(day.solve(Part::Two, &parsed, &context), None);
+    }
+}
@@ -0,0 +1,242 @@
+//! Levelled diagnostics for the solutions, written to stderr.
+//!
+//! Logging is off by default. It's switched on for every day with
+//! [`set_level`], e.g. from `--verbose`, or with the `AOC_LOG` environment
+//! variable, which takes a comma-separated list of levels for all days or
+//! for a single one, like `AOC_LOG=info,day13=trace`.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::RwLock;
+
+/// The environment variable read by [`init_from_env`]
+pub const ENV_VAR: &str = "AOC_LOG";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// What a solution found, like each packet pair's order
+    Info = 1,
+    /// How it got there, like where sand fell off the map
+    Debug = 2,
+    /// Every step, which can be a lot of output
+    Trace = 3,
+}
+
+impl Level {
+    /// The level for a number of `-v` flags, if any
+    pub fn from_verbosity(count: usize) -> Option<Level> {
+        match count {
+            0 => None,
+            1 => Some(Level::Info),
+            2 => Some(Level::Debug),
+            _ => Some(Level::Trace),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Option<Level>> {
+        match name {
+            "off" => Some(None),
+            "info" => Some(Some(Level::Info)),
+            "debug" => Some(Some(Level::Debug)),
+            "trace" => Some(Some(Level::Trace)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Which messages get written, for all days and for individual ones
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Filter {
+    default: Option<Level>,
+    days: Vec<(u8, Option<Level>)>,
+}
+
+impl Filter {
+    /// Parses a spec like `info,day13=trace,day14=off`
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut filter = Filter::default();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let level = |name: &str| {
+                Level::from_name(name).ok_or_else(|| {
+                    format!("`{name}` isn't a log level, expected off, info, debug or trace")
+                })
+            };
+            match entry.split_once('=') {
+                Some((day, name)) => {
+                    let number = day
+                        .strip_prefix("day")
+                        .and_then(|number| number.parse().ok())
+                        .ok_or_else(|| format!("expected a day like `day13`, found `{day}`"))?;
+                    filter.days.push((number, level(name)?));
+                }
+                None => filter.default = level(entry)?,
+            }
+        }
+        Ok(filter)
+    }
+
+    /// The most detailed level for a day, if anything is logged for it
+    fn level(&self, day: Option<u8>) -> Option<Level> {
+        day.and_then(|day| {
+            self.days
+                .iter()
+                .rev()
+                .find(|(number, _)| *number == day)
+                .map(|&(_, level)| level)
+        })
+        .unwrap_or(self.default)
+    }
+
+    fn max_level(&self) -> Option<Level> {
+        let days = self.days.iter().map(|&(_, level)| level);
+        days.chain([self.default]).max().flatten()
+    }
+}
+
+static FILTER: RwLock<Filter> = RwLock::new(Filter {
+    default: None,
+    days: Vec::new(),
+});
+
+/// The most detailed level of any day, so most calls can return without
+/// taking the lock
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_filter(filter: Filter) {
+    MAX_LEVEL.store(
+        filter.max_level().map_or(0, |level| level as u8),
+        Ordering::Relaxed,
+    );
+    *FILTER.write().unwrap() = filter;
+}
+
+/// Logs everything up to `level` for every day, keeping the per-day levels
+/// already set
+pub fn set_level(level: Level) {
+    let mut filter = FILTER.read().unwrap().clone();
+    filter.default = filter.default.max(Some(level));
+    set_filter(filter);
+}
+
+/// Sets the filter from [`ENV_VAR`], if it's set
+pub fn init_from_env() -> Result<(), String> {
+    match std::env::var(ENV_VAR) {
+        Ok(spec) => {
+            set_filter(Filter::parse(&spec).map_err(|err| format!("{ENV_VAR}: {err}"))?);
+            Ok(())
+        }
+        Err(_) => Ok(()),
+    }
+}
+
+/// The day a module belongs to, from a path like `advent_of_code_2022::day13`
+fn day_of(module: &str) -> Option<u8> {
+    let name = module.rsplit("::").find(|name| name.starts_with("day"))?;
+    name.strip_prefix("day")?.parse().ok()
+}
+
+/// Whether a message at `level` from `module` would be written. Use
+/// [`crate::log_enabled!`] rather than calling this directly.
+pub fn enabled(module: &str, level: Level) -> bool {
+    if level as u8 > MAX_LEVEL.load(Ordering::Relaxed) {
+        return false;
+    }
+    FILTER.read().unwrap().level(day_of(module)) >= Some(level)
+}
+
+/// Writes a message if its level is enabled. Use the [`crate::info!`],
+/// [`crate::debug!`] and [`crate::trace!`] macros rather than calling this
+/// directly.
+pub fn log(module: &str, level: Level, message: fmt::Arguments) {
+    if !enabled(module, level) {
+        return;
+    }
+    let source = day_of(module).map_or_else(
+        || module.rsplit("::").next().unwrap_or(module).to_string(),
+        |day| format!("day{day}"),
+    );
+    // Indent continuation lines so multi-line messages stay readable
+    let message = message.to_string().replace('\n', "\n    ");
+    eprintln!("[{source} {level}] {message}");
+}
+
+#[macro_export]
+macro_rules! log_enabled {
+    ($level:expr) => {
+        $crate::log::enabled(module_path!(), $level)
+    };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        $crate::log::log(module_path!(), $crate::log::Level::Info, format_args!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::log::log(module_path!(), $crate::log::Level::Debug, format_args!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        $crate::log::log(module_path!(), $crate::log::Level::Trace, format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters() {
+        let filter = Filter::parse("info, day13=trace,day14=off").unwrap();
+        assert_eq!(filter.level(Some(1)), Some(Level::Info));
+        assert_eq!(filter.level(None), Some(Level::Info));
+        assert_eq!(filter.level(Some(13)), Some(Level::Trace));
+        assert_eq!(filter.level(Some(14)), None);
+        assert_eq!(filter.max_level(), Some(Level::Trace));
+
+        assert_eq!(Filter::parse("").unwrap(), Filter::default());
+        assert_eq!(
+            Filter::parse("day13=loud").unwrap_err(),
+            "`loud` isn't a log level, expected off, info, debug or trace"
+        );
+        assert_eq!(
+            Filter::parse("13=debug").unwrap_err(),
+            "expected a day like `day13`, found `13`"
+        );
+    }
+
+    #[test]
+    fn days_of_modules() {
+        assert_eq!(day_of("advent_of_code_2022::day13"), Some(13));
+        assert_eq!(day_of("advent_of_code_2022::day13::tests"), Some(13));
+        assert_eq!(day_of("advent_of_code_2022::grid"), None);
+    }
+
+    #[test]
+    fn verbosity() {
+        assert_eq!(Level::from_verbosity(0), None);
+        assert_eq!(Level::from_verbosity(2), Some(Level::Debug));
+        assert_eq!(Level::from_verbosity(5), Some(Level::Trace));
+    }
+}
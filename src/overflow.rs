@@ -0,0 +1,355 @@
+//! Overflow checks for the arithmetic on the solutions' narrow integers,
+//! like day 7's `u32` directory sizes or day 9's `i16` coordinates.
+//!
+//! Arithmetic that could overflow goes through [`add`], [`sub`], [`mul`]
+//! and [`cast`], as does the arithmetic on [`Point`](crate::geometry::Point)s
+//! and [`Vec2`](crate::geometry::Vec2)s. Normally they're the plain
+//! operators, so an overflow wraps in release builds and panics in debug
+//! builds. While a part runs through [`checked`], as it does with
+//! `aoc run --checked`, an overflow instead stops the part with an
+//! [`Overflow`] saying where it happened and what the operands were.
+//!
+//! The narrow types themselves are [`Unsigned`], [`Signed`] and
+//! [`SmallSigned`], which building with `--features wide-ints` widens to
+//! 64 bits for inputs with bigger numbers than the puzzle's.
+
+use std::cell::Cell;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe, Location};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::json::Json;
+
+/// `u32`, or `u64` when built with the `wide-ints` feature
+#[cfg(not(feature = "wide-ints"))]
+pub type Unsigned = u32;
+/// `u32`, or `u64` when built with the `wide-ints` feature
+#[cfg(feature = "wide-ints")]
+pub type Unsigned = u64;
+
+/// `i32`, or `i64` when built with the `wide-ints` feature
+#[cfg(not(feature = "wide-ints"))]
+pub type Signed = i32;
+/// `i32`, or `i64` when built with the `wide-ints` feature
+#[cfg(feature = "wide-ints")]
+pub type Signed = i64;
+
+/// `i16`, or `i64` when built with the `wide-ints` feature
+#[cfg(not(feature = "wide-ints"))]
+pub type SmallSigned = i16;
+/// `i16`, or `i64` when built with the `wide-ints` feature
+#[cfg(feature = "wide-ints")]
+pub type SmallSigned = i64;
+
+/// The largest [`Unsigned`] a parameter can be, which is less than
+/// `u64::MAX` since parameters are `i64`s
+pub const UNSIGNED_MAX: i64 = if Unsigned::BITS < i64::BITS {
+    Unsigned::MAX as i64
+} else {
+    i64::MAX
+};
+
+/// The smallest [`Signed`] a parameter can be
+#[allow(clippy::unnecessary_cast)] // It's already an `i64` with `wide-ints`
+pub const SIGNED_MIN: i64 = Signed::MIN as i64;
+
+/// The largest [`Signed`] a parameter can be
+#[allow(clippy::unnecessary_cast)]
+pub const SIGNED_MAX: i64 = Signed::MAX as i64;
+
+/// An operation whose result didn't fit in its type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Overflow {
+    pub location: &'static Location<'static>,
+    pub lhs: i128,
+    /// `+`, `-`, `*` or `as`
+    pub operator: &'static str,
+    /// `None` for a cast
+    pub rhs: Option<i128>,
+    /// The type the result didn't fit in, like `u32`
+    pub type_name: &'static str,
+}
+
+impl Overflow {
+    /// The operation that overflowed, like `4294967295 + 1`
+    pub fn expression(&self) -> String {
+        match self.rhs {
+            Some(rhs) => format!("{} {} {rhs}", self.lhs, self.operator),
+            None => format!("{} as {}", self.lhs, self.type_name),
+        }
+    }
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "overflow at {}: {} doesn't fit in {}",
+            self.location,
+            self.expression(),
+            self.type_name
+        )
+    }
+}
+
+impl From<Overflow> for Json {
+    fn from(overflow: Overflow) -> Self {
+        Json::object()
+            .with("location", overflow.location.to_string())
+            .with("expression", overflow.expression())
+            .with("type", overflow.type_name)
+    }
+}
+
+/// A primitive integer, which every operand here is
+pub trait Int: Copy + fmt::Display {
+    const NAME: &'static str;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    fn wrapping_mul(self, rhs: Self) -> Self;
+    /// Every integer up to 64 bits fits in an `i128`
+    fn to_i128(self) -> i128;
+    fn try_from_i128(value: i128) -> Option<Self>;
+    /// Like an `as` cast from `value`
+    fn wrapping_from_i128(value: i128) -> Self;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {
+        $(
+            impl Int for $t {
+                const NAME: &'static str = stringify!($t);
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn wrapping_add(self, rhs: Self) -> Self {
+                    <$t>::wrapping_add(self, rhs)
+                }
+
+                fn wrapping_sub(self, rhs: Self) -> Self {
+                    <$t>::wrapping_sub(self, rhs)
+                }
+
+                fn wrapping_mul(self, rhs: Self) -> Self {
+                    <$t>::wrapping_mul(self, rhs)
+                }
+
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn try_from_i128(value: i128) -> Option<Self> {
+                    <$t>::try_from(value).ok()
+                }
+
+                fn wrapping_from_i128(value: i128) -> Self {
+                    value as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// How many threads are running a part through [`checked`], so the others
+/// only need to look at their own flag when one is
+static CHECKING_ANYWHERE: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static CHECKING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether overflows stop the current part, because it's being run through
+/// [`checked`]
+pub fn is_checking() -> bool {
+    CHECKING_ANYWHERE.load(Ordering::Relaxed) > 0 && CHECKING.get()
+}
+
+/// Runs `f`, returning the [`Overflow`] that stopped it if any of its
+/// arithmetic overflowed. Other panics carry on unwinding.
+pub fn checked<T>(f: impl FnOnce() -> T) -> Result<T, Overflow> {
+    /// Puts the flag back even if `f` unwinds
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CHECKING.set(self.0);
+            CHECKING_ANYWHERE.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    CHECKING_ANYWHERE.fetch_add(1, Ordering::Relaxed);
+    let _restore = Restore(CHECKING.replace(true));
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(value) => Ok(value),
+        Err(payload) => match payload.downcast::<Overflow>() {
+            Ok(overflow) => Err(*overflow),
+            Err(payload) => panic::resume_unwind(payload),
+        },
+    }
+}
+
+/// Stops the part with `overflow` if it's being checked, or panics with it
+/// otherwise
+pub fn report(overflow: Overflow) -> ! {
+    if is_checking() {
+        // Like a cancellation, this doesn't go through the panic hook
+        panic::resume_unwind(Box::new(overflow))
+    } else {
+        panic!("{overflow}")
+    }
+}
+
+#[track_caller]
+fn overflow<T: Int>(lhs: i128, operator: &'static str, rhs: Option<i128>) -> Overflow {
+    Overflow {
+        location: Location::caller(),
+        lhs,
+        operator,
+        rhs,
+        type_name: T::NAME,
+    }
+}
+
+/// `a + b`, or an [`Overflow`] at the caller if it doesn't fit
+#[track_caller]
+pub fn try_add<T: Int>(a: T, b: T) -> Result<T, Overflow> {
+    match a.checked_add(b) {
+        Some(result) => Ok(result),
+        None => Err(overflow::<T>(a.to_i128(), "+", Some(b.to_i128()))),
+    }
+}
+
+/// `a - b`, or an [`Overflow`] at the caller if it doesn't fit
+#[track_caller]
+pub fn try_sub<T: Int>(a: T, b: T) -> Result<T, Overflow> {
+    match a.checked_sub(b) {
+        Some(result) => Ok(result),
+        None => Err(overflow::<T>(a.to_i128(), "-", Some(b.to_i128()))),
+    }
+}
+
+/// `a * b`, or an [`Overflow`] at the caller if it doesn't fit
+#[track_caller]
+pub fn try_mul<T: Int>(a: T, b: T) -> Result<T, Overflow> {
+    match a.checked_mul(b) {
+        Some(result) => Ok(result),
+        None => Err(overflow::<T>(a.to_i128(), "*", Some(b.to_i128()))),
+    }
+}
+
+/// `value as U`, or an [`Overflow`] at the caller if it doesn't fit
+#[track_caller]
+pub fn try_cast<T: Int, U: Int>(value: T) -> Result<U, Overflow> {
+    match U::try_from_i128(value.to_i128()) {
+        Some(value) => Ok(value),
+        None => Err(overflow::<U>(value.to_i128(), "as", None)),
+    }
+}
+
+/// `a + b`, which [reports](report) an overflow
+#[track_caller]
+pub fn add<T: Int>(a: T, b: T) -> T {
+    match try_add(a, b) {
+        Ok(sum) => sum,
+        Err(_) if !cfg!(debug_assertions) && !is_checking() => a.wrapping_add(b),
+        Err(overflow) => report(overflow),
+    }
+}
+
+/// `a - b`, which [reports](report) an overflow
+#[track_caller]
+pub fn sub<T: Int>(a: T, b: T) -> T {
+    match try_sub(a, b) {
+        Ok(difference) => difference,
+        Err(_) if !cfg!(debug_assertions) && !is_checking() => a.wrapping_sub(b),
+        Err(overflow) => report(overflow),
+    }
+}
+
+/// `a * b`, which [reports](report) an overflow
+#[track_caller]
+pub fn mul<T: Int>(a: T, b: T) -> T {
+    match try_mul(a, b) {
+        Ok(product) => product,
+        Err(_) if !cfg!(debug_assertions) && !is_checking() => a.wrapping_mul(b),
+        Err(overflow) => report(overflow),
+    }
+}
+
+/// `value as U`, which [reports](report) an overflow
+#[track_caller]
+pub fn cast<T: Int, U: Int>(value: T) -> U {
+    match try_cast(value) {
+        Ok(value) => value,
+        Err(_) if !is_checking() => U::wrapping_from_i128(value.to_i128()),
+        Err(overflow) => report(overflow),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cancel::{self, Cancelled};
+
+    #[test]
+    fn unchecked() {
+        assert!(!is_checking());
+        assert_eq!(add(2u32, 3), 5);
+        assert_eq!(sub(2i16, 3), -1);
+        assert_eq!(mul(6u64, 7), 42);
+        // Casts truncate like `as` does
+        assert_eq!(cast::<i64, i32>(1 << 32), 0);
+        assert_eq!(cast::<i32, u32>(-1), u32::MAX);
+    }
+
+    #[test]
+    fn reporting_overflows() {
+        let line = line!() + 1;
+        let overflow = checked(|| add(u32::MAX, 1)).unwrap_err();
+        assert_eq!(overflow.location.file(), file!());
+        assert_eq!(overflow.location.line(), line);
+        assert_eq!(overflow.expression(), "4294967295 + 1");
+        assert_eq!(
+            overflow.to_string(),
+            format!(
+                "overflow at {}: 4294967295 + 1 doesn't fit in u32",
+                overflow.location
+            )
+        );
+
+        let overflow = checked(|| cast::<i64, i32>(1 << 32)).unwrap_err();
+        assert_eq!(overflow.expression(), "4294967296 as i32");
+        assert_eq!(checked(|| sub(0u8, 1)).unwrap_err().expression(), "0 - 1");
+        assert_eq!(checked(|| mul(2, 3)), Ok(6));
+        assert!(!is_checking());
+    }
+
+    #[test]
+    fn other_unwinds() {
+        let cancelled = cancel::catch(|| checked(|| cancel::NEVER.check()).unwrap());
+        assert_eq!(cancelled, Ok(()));
+        let cancellation = cancel::Cancellation::new();
+        cancellation.cancel();
+        assert_eq!(
+            cancel::catch(|| checked(|| cancellation.check())),
+            Err(Cancelled)
+        );
+        assert!(!is_checking());
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::parse::ParseError;
+use crate::{get_day, toml, Day};
+
+/// A value a solution uses that comes from the puzzle text rather than the
+/// input, like the number of rounds to play, so it can be changed at runtime
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: i64,
+    /// The smallest value that makes sense, e.g. 1 for a length
+    pub min: i64,
+    /// The largest value the solution's types can hold
+    pub max: i64,
+    pub description: &'static str,
+}
+
+impl Param {
+    pub const fn new(name: &'static str, default: i64, description: &'static str) -> Self {
+        Param {
+            name,
+            default,
+            min: 0,
+            max: i64::MAX,
+            description,
+        }
+    }
+
+    pub const fn at_least(self, min: i64) -> Self {
+        Param { min, ..self }
+    }
+
+    pub const fn at_most(self, max: i64) -> Self {
+        Param { max, ..self }
+    }
+
+    fn describe_range(&self) -> String {
+        match (self.min, self.max) {
+            (min, i64::MAX) => format!("at least {min}"),
+            (min, max) => format!("from {min} to {max}"),
+        }
+    }
+}
+
+/// Values that override the defaults of any day's parameters
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<(u8, &'static str), i64>,
+}
+
+/// Why a parameter couldn't be set
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParamError {
+    NoSuchDay(u8),
+    /// The day doesn't have a parameter with this name
+    Unknown {
+        day: u8,
+        name: String,
+    },
+    OutOfRange {
+        day: u8,
+        param: Param,
+        value: i64,
+    },
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParamError::NoSuchDay(day) => write!(f, "there is no solution for day {day}"),
+            ParamError::Unknown { day, name } => {
+                let params = get_day(*day).map_or(&[][..], |day| day.params);
+                write!(f, "day {day} has no parameter `{name}`")?;
+                if params.is_empty() {
+                    write!(f, ", it doesn't have any")
+                } else {
+                    let names: Vec<_> = params.iter().map(|param| param.name).collect();
+                    write!(f, ", expected one of {}", names.join(", "))
+                }
+            }
+            ParamError::OutOfRange { day, param, value } => write!(
+                f,
+                "day {day} parameter `{}` must be {}, not {value}",
+                param.name,
+                param.describe_range()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+impl Params {
+    pub const fn new() -> Self {
+        Params {
+            values: BTreeMap::new(),
+        }
+    }
+
+    pub fn set(&mut self, day: u8, name: &str, value: i64) -> Result<(), ParamError> {
+        let param = get_day(day)
+            .ok_or(ParamError::NoSuchDay(day))?
+            .params
+            .iter()
+            .find(|param| param.name == name)
+            .ok_or_else(|| ParamError::Unknown {
+                day,
+                name: name.to_string(),
+            })?;
+        if !(param.min..=param.max).contains(&value) {
+            return Err(ParamError::OutOfRange {
+                day,
+                param: *param,
+                value,
+            });
+        }
+        self.values.insert((day, param.name), value);
+        Ok(())
+    }
+
+    /// Sets a parameter from an assignment like `day15.row=10`
+    pub fn set_from_arg(&mut self, arg: &str) -> Result<(), String> {
+        let invalid = || format!("expected a parameter like `day15.row=10`, found `{arg}`");
+        let (name, value) = arg.split_once('=').ok_or_else(invalid)?;
+        let (day, name) = name.split_once('.').ok_or_else(invalid)?;
+        let day = day
+            .strip_prefix("day")
+            .and_then(|day| day.parse().ok())
+            .ok_or_else(invalid)?;
+        let value = value.parse().map_err(|_| invalid())?;
+        self.set(day, name, value).map_err(|err| err.to_string())
+    }
+
+    /// Reads parameters from a file with a `[dayN]` table for each day,
+    /// like `[day15]` followed by `row = 10`
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut params = Params::new();
+        params.extend_from_file(text)?;
+        Ok(params)
+    }
+
+    /// Like [`Params::parse`], but adds to the parameters already set
+    pub fn extend_from_file(&mut self, text: &str) -> Result<(), ParseError> {
+        for table in toml::parse(text, "a `[dayN]` table")? {
+            let number = toml::day_number(&table)?;
+            let day = get_day(number).ok_or_else(|| {
+                let column = table.line.column();
+                table
+                    .line
+                    .error_at(column, table.name, "a day with a solution")
+            })?;
+
+            for entry in table.entries {
+                let param = day
+                    .params
+                    .iter()
+                    .find(|param| param.name == entry.key)
+                    .ok_or_else(|| {
+                        let expected = if day.params.is_empty() {
+                            format!("no parameters for day {number}")
+                        } else {
+                            let names: Vec<_> = day.params.iter().map(|param| param.name).collect();
+                            format!("a parameter of day {number} ({})", names.join(", "))
+                        };
+                        entry
+                            .line
+                            .error_at(entry.line.column(), entry.key, expected)
+                    })?;
+
+                let mut value_line = entry.line;
+                value_line.take_until("=");
+                value_line.take_while(char::is_whitespace);
+                let value = match entry.value {
+                    toml::Value::Integer(value) if (param.min..=param.max).contains(&value) => {
+                        value
+                    }
+                    toml::Value::Integer(value) => {
+                        return Err(value_line.error_at(
+                            value_line.column(),
+                            value.to_string(),
+                            format!("a value that is {}", param.describe_range()),
+                        ))
+                    }
+                    toml::Value::String(_) => return Err(value_line.error("an integer")),
+                };
+                self.values.insert((number, param.name), value);
+            }
+        }
+        Ok(())
+    }
+
+    /// The value of one of `day`'s parameters. Panics if the day doesn't
+    /// have a parameter called `name`.
+    pub fn get(&self, day: &Day, name: &str) -> i64 {
+        let param = day
+            .params
+            .iter()
+            .find(|param| param.name == name)
+            .unwrap_or_else(|| panic!("day {} has no parameter `{name}`", day.number));
+        self.values
+            .get(&(day.number, param.name))
+            .copied()
+            .unwrap_or(param.default)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overriding_defaults() {
+        let day = get_day(15).unwrap();
+        let mut params = Params::new();
+        assert_eq!(params.get(day, "row"), 2000000);
+        params.set_from_arg("day15.row=10").unwrap();
+        assert_eq!(params.get(day, "row"), 10);
+        assert_eq!(params.get(day, "max_coord"), 4000000);
+    }
+
+    #[test]
+    fn invalid_params() {
+        let mut params = Params::new();
+        assert_eq!(
+            params.set_from_arg("day6.packet_marker=0").unwrap_err(),
+            "day 6 parameter `packet_marker` must be from 1 to 26, not 0"
+        );
+        assert_eq!(
+            params.set_from_arg("day6.message_marker=27").unwrap_err(),
+            "day 6 parameter `message_marker` must be from 1 to 26, not 27"
+        );
+        assert_eq!(
+            params
+                .set_from_arg("day9.part2_tail=10000000000")
+                .unwrap_err(),
+            "day 9 parameter `part2_tail` must be from 1 to 10000, not 10000000000"
+        );
+        assert_eq!(
+            params.set_from_arg("day15.rows=10").unwrap_err(),
+            "day 15 has no parameter `rows`, expected one of row, max_coord"
+        );
+        assert_eq!(
+            params.set_from_arg("day1.size=10").unwrap_err(),
+            "day 1 has no parameter `size`, it doesn't have any"
+        );
+        assert_eq!(
+            params.set_from_arg("day15.row").unwrap_err(),
+            "expected a parameter like `day15.row=10`, found `day15.row`"
+        );
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn params_file() {
+        let params =
+            Params::parse("[day11]\npart2_rounds = 1000\n\n[day10]\ncrt_width = 20").unwrap();
+        assert_eq!(params.get(get_day(11).unwrap(), "part2_rounds"), 1000);
+        assert_eq!(params.get(get_day(10).unwrap(), "crt_width"), 20);
+
+        let error = Params::parse("[day11]\nrounds = 1000").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected a parameter of day 11 (part1_rounds, part2_rounds), \
+             found `rounds`"
+        );
+        let error = Params::parse("[day11]\npart1_rounds = \"20\"").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 16: expected an integer, found `\"`"
+        );
+        let error = Params::parse("[day9]\npart2_tail = 0").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 14: expected a value that is from 1 to 10000, found `0`"
+        );
+    }
+}
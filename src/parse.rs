@@ -0,0 +1,392 @@
+use std::any::type_name;
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+/// An error in a puzzle input, pointing at where it went wrong
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Parsers don't know which day they belong to, so this is filled in
+    /// by [`crate::Day::parse`]
+    pub day: Option<u8>,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in characters
+    pub column: usize,
+    /// The text that couldn't be parsed, empty at the end of a line
+    pub found: String,
+    /// What the parser was looking for instead
+    pub expected: String,
+}
+
+impl ParseError {
+    /// The input stopped before line `line` when there was more to parse
+    pub fn end_of_input(line: usize, expected: impl Into<String>) -> Self {
+        ParseError {
+            day: None,
+            line,
+            column: 1,
+            found: String::new(),
+            expected: expected.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}, ")?;
+        }
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            write!(f, "end of line")
+        } else {
+            write!(f, "`{}`", self.found)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The byte order mark some editors start UTF-8 files with
+pub(crate) const BOM: &str = "\u{feff}";
+
+/// Undoes what editors can do to an input when saving it, so parsers only
+/// see the puzzle's text: drops a [`BOM`], `\r\n` line endings and any
+/// whitespace at the end of lines or after the last one, and ends the input
+/// with a single newline. Inputs that are already like that are borrowed.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix(BOM).unwrap_or(input);
+    let text = input.trim_end();
+    let clean = text
+        .split('\n')
+        .all(|line| !line.ends_with(char::is_whitespace))
+        && matches!(&input[text.len()..], "" | "\n");
+    if clean {
+        return Cow::Borrowed(input);
+    }
+    let mut normal = String::with_capacity(text.len() + 1);
+    for line in text.lines() {
+        normal.push_str(line.trim_end());
+        normal.push('\n');
+    }
+    Cow::Owned(normal)
+}
+
+/// Iterates over the lines of the input, numbered for error reporting
+pub fn lines(input: &str) -> Lines<'_> {
+    Lines {
+        lines: input.lines(),
+        last: 0,
+    }
+}
+
+pub struct Lines<'a> {
+    lines: std::str::Lines<'a>,
+    /// The number of the last line returned
+    last: usize,
+}
+
+impl<'a> Lines<'a> {
+    /// Takes the next line, which the input must not end before
+    pub fn expect_line(&mut self, expected: &str) -> Result<Line<'a>, ParseError> {
+        self.next()
+            .ok_or_else(|| ParseError::end_of_input(self.last + 1, expected))
+    }
+
+    /// An error for input that ended early
+    pub fn end_of_input(&self, expected: impl Into<String>) -> ParseError {
+        ParseError::end_of_input(self.last + 1, expected)
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Line<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let text = self.lines.next()?;
+        self.last += 1;
+        Some(Line::new(self.last, text))
+    }
+}
+
+/// A cursor over one line of input that knows its position, so anything
+/// that fails to parse can be reported as a [`ParseError`]
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    number: usize,
+    text: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(number: usize, text: &'a str) -> Self {
+        Line {
+            number,
+            text,
+            rest: text,
+        }
+    }
+
+    /// 1-based line number
+    pub fn line_number(&self) -> usize {
+        self.number
+    }
+
+    /// The whole line, including anything already parsed
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The part of the line that hasn't been parsed yet
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// 1-based column of the next character to parse
+    pub fn column(&self) -> usize {
+        self.column_of(self.text.len() - self.rest.len())
+    }
+
+    fn column_of(&self, byte_offset: usize) -> usize {
+        self.text[..byte_offset].chars().count() + 1
+    }
+
+    /// An error for whatever comes next on the line
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        self.error_at(self.column(), next_token(self.rest), expected)
+    }
+
+    /// An error for something other than the next token, e.g. the whole line
+    pub fn error_at(
+        &self,
+        column: usize,
+        found: impl Into<String>,
+        expected: impl Into<String>,
+    ) -> ParseError {
+        ParseError {
+            day: None,
+            line: self.number,
+            column,
+            found: found.into(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Skips over `literal`, which must come next
+    pub fn expect(&mut self, literal: &str) -> Result<(), ParseError> {
+        match self.rest.strip_prefix(literal) {
+            Some(rest) => {
+                self.rest = rest;
+                Ok(())
+            }
+            None => Err(self.error(format!("`{literal}`"))),
+        }
+    }
+
+    /// Skips over `literal` if it comes next
+    pub fn eat(&mut self, literal: &str) -> bool {
+        self.expect(literal).is_ok()
+    }
+
+    /// Parses an optionally negative integer
+    pub fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let sign = usize::from(self.rest.starts_with('-'));
+        let len = sign
+            + self.rest[sign..]
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(self.rest.len() - sign);
+        if len == sign {
+            return Err(self.error("a number"));
+        }
+
+        let (number, rest) = self.rest.split_at(len);
+        let number = number.parse().map_err(|_| {
+            let type_name = type_name::<T>();
+            self.error_at(
+                self.column(),
+                number,
+                format!("a number that fits in {type_name}"),
+            )
+        })?;
+        self.rest = rest;
+        Ok(number)
+    }
+
+    /// Takes the next character, which must be one of the `options`, and
+    /// returns the value it stands for
+    pub fn one_of<T: Copy>(&mut self, options: &[(char, T)]) -> Result<T, ParseError> {
+        let next = self.rest.chars().next();
+        match options.iter().find(|(c, _)| Some(*c) == next) {
+            Some(&(c, value)) => {
+                self.rest = &self.rest[c.len_utf8()..];
+                Ok(value)
+            }
+            None => Err(self.error(describe_options(options))),
+        }
+    }
+
+    /// Takes the next character
+    pub fn next_char(&mut self) -> Option<char> {
+        let c = self.rest.chars().next()?;
+        self.rest = &self.rest[c.len_utf8()..];
+        Some(c)
+    }
+
+    pub fn peek_char(&self) -> Option<char> {
+        self.rest.chars().next()
+    }
+
+    /// Takes characters for as long as they match `predicate`
+    pub fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let len = self
+            .rest
+            .find(|c: char| !predicate(c))
+            .unwrap_or(self.rest.len());
+        let (taken, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        taken
+    }
+
+    /// Takes everything before `delimiter` and skips over the delimiter, or
+    /// returns `None` if it isn't on the rest of the line
+    pub fn take_until(&mut self, delimiter: &str) -> Option<&'a str> {
+        let (taken, rest) = self.rest.split_once(delimiter)?;
+        self.rest = rest;
+        Some(taken)
+    }
+
+    /// Takes everything up to the end of the line
+    pub fn take_rest(&mut self) -> &'a str {
+        let rest = self.rest;
+        self.rest = "";
+        rest
+    }
+
+    /// Checks that there's nothing left on the line
+    pub fn finish(&self) -> Result<(), ParseError> {
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+}
+
+/// Formats a list of characters like "`A`, `B` or `C`"
+fn describe_options<T>(options: &[(char, T)]) -> String {
+    let mut description = String::new();
+    for (index, (option, _)) in options.iter().enumerate() {
+        if index > 0 {
+            description += if index == options.len() - 1 {
+                " or "
+            } else {
+                ", "
+            };
+        }
+        description += &format!("`{option}`");
+    }
+    description
+}
+
+/// The word, number or single piece of punctuation at the start of `text`
+fn next_token(text: &str) -> &str {
+    let len = text
+        .find(|c: char| !c.is_alphanumeric())
+        .unwrap_or(text.len());
+    if len == 0 {
+        text.chars().next().map_or("", |c| &text[..c.len_utf8()])
+    } else {
+        &text[..len]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizing() {
+        assert!(matches!(normalize("a\n\nb\n"), Cow::Borrowed("a\n\nb\n")));
+        assert!(matches!(normalize("a b"), Cow::Borrowed("a b")));
+        assert!(matches!(normalize("\u{feff}a"), Cow::Borrowed("a")));
+        assert_eq!(normalize("a\r\n\r\nb\r\n"), "a\n\nb\n");
+        assert_eq!(normalize("  a  \t\nb \n\n \n"), "  a\nb\n");
+        assert_eq!(normalize("\u{feff}\r\n"), "");
+    }
+
+    #[test]
+    fn parsing_a_line() {
+        let mut line = Line::new(1, "move 3 from -1 to 2");
+        line.expect("move ").unwrap();
+        assert_eq!(line.number::<usize>().unwrap(), 3);
+        line.expect(" from ").unwrap();
+        assert_eq!(line.number::<i32>().unwrap(), -1);
+        line.expect(" to ").unwrap();
+        assert_eq!(line.number::<u8>().unwrap(), 2);
+        line.finish().unwrap();
+    }
+
+    #[test]
+    fn error_position() {
+        let mut line = Line::new(7, "2-4,x-8");
+        line.number::<u32>().unwrap();
+        line.expect("-").unwrap();
+        line.number::<u32>().unwrap();
+        line.expect(",").unwrap();
+        let error = line.number::<u32>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseError {
+                day: None,
+                line: 7,
+                column: 5,
+                found: "x".to_string(),
+                expected: "a number".to_string(),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "line 7, column 5: expected a number, found `x`"
+        );
+    }
+
+    #[test]
+    fn number_out_of_range() {
+        let error = Line::new(1, "300").number::<u8>().unwrap_err();
+        assert_eq!(error.expected, "a number that fits in u8");
+        assert_eq!(error.found, "300");
+    }
+
+    #[test]
+    fn describing_options() {
+        let mut line = Line::new(2, "B Y");
+        assert_eq!(line.one_of(&[('A', 1), ('B', 2), ('C', 3)]), Ok(2));
+        let mut line = Line::new(2, "D Y");
+        let error = line.one_of(&[('A', 1), ('B', 2), ('C', 3)]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected `A`, `B` or `C`, found `D`"
+        );
+    }
+
+    #[test]
+    fn unexpected_end_of_line() {
+        let mut line = Line::new(3, "noop");
+        line.expect("noop").unwrap();
+        assert!(line.finish().is_ok());
+        let error = line.expect(" ").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 5: expected ` `, found end of line"
+        );
+    }
+}
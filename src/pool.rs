@@ -0,0 +1,128 @@
+//! A fixed number of worker threads sharing a queue of tasks, where running
+//! a task can queue more, like solving each part of a day once its input
+//! has been parsed.
+
+use std::collections::VecDeque;
+use std::num::NonZeroUsize;
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+/// The tasks waiting for a worker
+pub struct Queue<T> {
+    state: Mutex<State<T>>,
+    changed: Condvar,
+}
+
+struct State<T> {
+    tasks: VecDeque<T>,
+    /// Tasks that have been taken but not finished, which may still queue
+    /// more
+    running: usize,
+}
+
+/// As many threads as the machine can run at once, or 1 if that can't be
+/// found out
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Runs `work` on every task in `tasks` and on every task queued by `work`,
+/// using `threads` workers. Returns once they've all finished.
+pub fn run<T: Send>(
+    threads: usize,
+    tasks: impl IntoIterator<Item = T>,
+    work: impl Fn(T, &Queue<T>) + Sync,
+) {
+    let queue = Queue {
+        state: Mutex::new(State {
+            tasks: tasks.into_iter().collect(),
+            running: 0,
+        }),
+        changed: Condvar::new(),
+    };
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| {
+                while let Some(task) = queue.take() {
+                    let _finished = Finished(&queue);
+                    work(task, &queue);
+                }
+            });
+        }
+    });
+}
+
+impl<T> Queue<T> {
+    pub fn push(&self, task: T) {
+        self.state.lock().unwrap().tasks.push_back(task);
+        self.changed.notify_one();
+    }
+
+    /// Waits for a task, or returns `None` once there are none left and
+    /// none running that could queue more
+    fn take(&self) -> Option<T> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(task) = state.tasks.pop_front() {
+                state.running += 1;
+                return Some(task);
+            }
+            if state.running == 0 {
+                return None;
+            }
+            state = self.changed.wait(state).unwrap();
+        }
+    }
+}
+
+/// Marks a task as finished when dropped, even if it panicked, so the other
+/// workers don't wait for it forever
+struct Finished<'a, T>(&'a Queue<T>);
+
+impl<T> Drop for Finished<'_, T> {
+    fn drop(&mut self) {
+        let mut state = self.0.state.lock().unwrap();
+        state.running -= 1;
+        if state.running == 0 && state.tasks.is_empty() {
+            self.0.changed.notify_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn tasks_queueing_tasks() {
+        for threads in [1, 4] {
+            let total = AtomicUsize::new(0);
+            // Each task n queues n - 1 down to 0, so 3 runs 8 tasks in all
+            run(threads, [3, 3], |n, queue| {
+                total.fetch_add(1, Ordering::Relaxed);
+                for smaller in 0..n {
+                    queue.push(smaller);
+                }
+            });
+            assert_eq!(total.into_inner(), 16);
+        }
+    }
+
+    #[test]
+    fn no_tasks() {
+        run(3, Vec::<u8>::new(), |_, _| unreachable!());
+    }
+
+    #[test]
+    fn panicking_task() {
+        let result = std::panic::catch_unwind(|| {
+            run(2, [0, 1, 2, 3], |n, _| {
+                if n == 1 {
+                    panic!("task failed");
+                }
+            });
+        });
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,154 @@
+//! Progress reports from the solutions' long loops, like the rounds of
+//! keep away in day 11.
+//!
+//! Loops call [`report`] as they go, which does nothing unless the part is
+//! being run through [`watch`], as the runner does when its settings have a
+//! [`Listener`]. Reports are only passed on every so often, so [`report`] is
+//! cheap enough to call on every iteration.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::allocations;
+use crate::Part;
+
+/// How far a part has got
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Progress {
+    pub done: usize,
+    /// `None` when the loop doesn't know how long it will run for
+    pub total: Option<usize>,
+    /// What's being counted, like `rounds`
+    pub unit: &'static str,
+}
+
+/// Gets the progress of every part run through [`watch`]. It can be called
+/// from several threads at once.
+pub trait Listener: Send + Sync {
+    fn update(&self, day: u8, part: Part, progress: Progress);
+
+    /// Called once the part has stopped, whether it reported any progress
+    /// or not
+    fn finished(&self, day: u8, part: Part);
+}
+
+impl fmt::Debug for dyn Listener {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Listener")
+    }
+}
+
+struct Watched {
+    listener: Arc<dyn Listener>,
+    day: u8,
+    part: Part,
+}
+
+thread_local! {
+    static WATCHED: RefCell<Option<Watched>> = const { RefCell::new(None) };
+}
+
+/// About how many updates a loop with a known total passes on
+const UPDATES: usize = 1000;
+
+/// How often a loop without a total passes on an update
+const UNKNOWN_TOTAL_STEP: usize = 1024;
+
+/// Reports that `done` out of `total` `unit`s have been done
+pub fn report(done: usize, total: Option<usize>, unit: &'static str) {
+    let step = total.map_or(UNKNOWN_TOTAL_STEP, |total| (total / UPDATES).max(1));
+    if !done.is_multiple_of(step) && Some(done) != total {
+        return;
+    }
+    WATCHED.with_borrow(|watched| {
+        if let Some(watched) = watched {
+            let progress = Progress { done, total, unit };
+            allocations::ignoring(|| watched.listener.update(watched.day, watched.part, progress));
+        }
+    });
+}
+
+/// Runs `f`, which solves `part` of `day`, passing the progress it reports
+/// to `listener`
+pub fn watch<T>(listener: &Arc<dyn Listener>, day: u8, part: Part, f: impl FnOnce() -> T) -> T {
+    /// Stops watching even if `f` unwinds, e.g. when it's cancelled
+    struct Finished<'a>(&'a Arc<dyn Listener>, u8, Part);
+
+    impl Drop for Finished<'_> {
+        fn drop(&mut self) {
+            WATCHED.set(None);
+            self.0.finished(self.1, self.2);
+        }
+    }
+
+    WATCHED.set(Some(Watched {
+        listener: Arc::clone(listener),
+        day,
+        part,
+    }));
+    let _finished = Finished(listener, day, part);
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl Listener for Recorder {
+        fn update(&self, day: u8, part: Part, progress: Progress) {
+            let Progress { done, total, unit } = progress;
+            let total = total.map_or("?".to_string(), |total| total.to_string());
+            let update = format!("{day}.{part} {done}/{total} {unit}");
+            self.0.lock().unwrap().push(update);
+        }
+
+        fn finished(&self, day: u8, part: Part) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("{day}.{part} finished"));
+        }
+    }
+
+    #[test]
+    fn watching_a_part() {
+        let recorder = Arc::new(Recorder::default());
+        let listener: Arc<dyn Listener> = recorder.clone();
+        let answer = watch(&listener, 11, Part::Two, || {
+            for round in 1..=3000 {
+                report(round, Some(3000), "rounds");
+            }
+            5
+        });
+        assert_eq!(answer, 5);
+        // Not watched any more
+        report(3000, Some(3000), "rounds");
+
+        let updates = recorder.0.lock().unwrap();
+        assert_eq!(updates.len(), 1001);
+        assert_eq!(updates[0], "11.2 3/3000 rounds");
+        assert_eq!(updates[999], "11.2 3000/3000 rounds");
+        assert_eq!(updates[1000], "11.2 finished");
+    }
+
+    #[test]
+    fn unknown_totals() {
+        let recorder = Arc::new(Recorder::default());
+        let listener: Arc<dyn Listener> = recorder.clone();
+        watch(&listener, 14, Part::One, || {
+            for grain in 1..=3000 {
+                report(grain, None, "grains");
+            }
+        });
+        let updates = recorder.0.lock().unwrap();
+        assert_eq!(
+            *updates,
+            ["14.1 1024/? grains", "14.1 2048/? grains", "14.1 finished"]
+        );
+    }
+}
@@ -0,0 +1,81 @@
+//! A small seeded random number generator, so generated inputs can be
+//! reproduced from their seed.
+
+use std::ops::RangeInclusive;
+
+/// SplitMix64, which is fast, has a 64-bit state and passes the usual
+/// statistical tests. Not for anything that needs to be unpredictable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from 0 up to but not including `n`, which must not be 0
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "can't pick a number below 0");
+        // Multiplying keeps the bias negligible without a division
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// A number in `range`, which must not be empty
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "can't pick from an empty range");
+        let span = end.abs_diff(start) as u128 + 1;
+        let offset = (self.next_u64() as u128 * span) >> 64;
+        start.wrapping_add(offset as i64)
+    }
+
+    /// `true` with the given probability
+    pub fn chance(&mut self, probability: f64) -> bool {
+        // The top 53 bits give every f64 in [0, 1) with a step of 2^-53
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    /// One of `items`, which must not be empty
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeds_are_reproducible() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(42), numbers(42));
+        assert_ne!(numbers(42), numbers(43));
+    }
+
+    #[test]
+    fn staying_in_range() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            assert!(rng.below(7) < 7);
+            assert!((-3..=3).contains(&rng.range(-3..=3)));
+        }
+        assert_eq!(rng.range(5..=5), 5);
+        let full = rng.range(i64::MIN..=i64::MAX);
+        assert!((i64::MIN..=i64::MAX).contains(&full));
+        assert!(!rng.chance(0.0));
+        assert!(rng.chance(1.0));
+    }
+}
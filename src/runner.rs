@@ -0,0 +1,587 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::BufRead;
+use std::mem;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::allocations::{self, Allocations};
+use crate::cancel::{self, Cancellation, Cancelled};
+use crate::differential;
+use crate::json::Json;
+use crate::overflow::{self, Overflow};
+use crate::params::Params;
+use crate::pool;
+use crate::progress::{self, Listener};
+use crate::trace::{self, Sink};
+use crate::{Answer, Context, Day, Parsed, Part};
+
+/// The outcome of parsing a day's input and solving some of its parts
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayResult {
+    pub day: u8,
+    /// `None` if the input couldn't be loaded or parsed, or was streamed
+    pub parse_time: Option<Duration>,
+    pub parts: Vec<PartResult>,
+    pub error: Option<String>,
+    /// Whether parsing or one of the parts panicked, which is a bug in the
+    /// solution rather than a problem with its input
+    pub panicked: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub part: Part,
+    /// `None` if this part hasn't been solved yet, or didn't finish
+    pub answer: Option<Answer>,
+    pub time: Duration,
+    /// Whether the part ran out of time. The time it took can be longer
+    /// than the timeout, since parts only stop at their cancellation checks.
+    pub timed_out: bool,
+    /// What the part allocated, if built with the `count-allocations`
+    /// feature
+    pub allocations: Option<Allocations>,
+    /// Why the input has no answer for this part, according to its
+    /// solution
+    pub error: Option<String>,
+    /// The overflow that stopped the part, if it was
+    /// [checked](Settings::checked)
+    pub overflow: Option<Overflow>,
+    /// The message of the panic that stopped the part
+    pub panic: Option<String>,
+}
+
+impl DayResult {
+    /// A day that couldn't be run at all, e.g. because its input couldn't
+    /// be read
+    pub fn failed(day: u8, error: impl ToString) -> Self {
+        DayResult {
+            day,
+            parse_time: None,
+            parts: Vec::new(),
+            error: Some(error.to_string()),
+            panicked: false,
+        }
+    }
+
+    pub fn to_json(&self) -> Json {
+        let nanos = |duration: Duration| u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        let parts = self
+            .parts
+            .iter()
+            .map(|part| {
+                Json::object()
+                    .with("part", part.part.number())
+                    .with("answer", part.answer.as_ref().map(Json::from))
+                    .with("runtime_ns", nanos(part.time))
+                    .with("timed_out", part.timed_out)
+                    .with("allocations", part.allocations.map(Json::from))
+                    .with("error", part.error.clone())
+                    .with("overflow", part.overflow.map(Json::from))
+                    .with("panic", part.panic.clone())
+            })
+            .collect::<Vec<_>>();
+        Json::object()
+            .with("day", self.day)
+            .with("parse_ns", self.parse_time.map(nanos))
+            .with("parts", parts)
+            .with("error", self.error.clone())
+            .with("panicked", self.panicked)
+    }
+}
+
+impl From<Allocations> for Json {
+    fn from(allocations: Allocations) -> Self {
+        Json::object()
+            .with("count", allocations.count)
+            .with("bytes", allocations.bytes)
+            .with("peak_bytes", allocations.peak_bytes)
+    }
+}
+
+impl From<&Answer> for Json {
+    fn from(answer: &Answer) -> Self {
+        match answer {
+            Answer::Number(number) => Json::Integer(*number),
+            Answer::Text(text) => Json::String(text.clone()),
+        }
+    }
+}
+
+/// How [`run_days`] runs each day
+#[derive(Clone, Debug)]
+pub struct Settings {
+    pub parts: Vec<Part>,
+    pub params: Params,
+    pub threads: usize,
+    /// How long each part can take before it's cancelled
+    pub timeout: Option<Duration>,
+    /// Gets the progress the parts report
+    pub progress: Option<Arc<dyn Listener>>,
+    /// Gets the events the simulations emit
+    pub trace: Option<Arc<dyn Sink>>,
+    /// Whether parsing and solving stop at the first overflow, reporting
+    /// it, instead of wrapping or panicking
+    pub checked: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            parts: Part::ALL.to_vec(),
+            params: Params::new(),
+            threads: 1,
+            timeout: None,
+            progress: None,
+            trace: None,
+            checked: false,
+        }
+    }
+}
+
+/// A day for [`run_days`] to run, with its input or the error from loading it
+pub struct Job<'a> {
+    pub day: &'static Day,
+    pub input: Result<Cow<'a, str>, String>,
+}
+
+/// How long [`run_days`] took in all
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Summary {
+    pub days: usize,
+    pub threads: usize,
+    pub wall_time: Duration,
+    /// The time spent parsing and solving, added up over every thread
+    pub cpu_time: Duration,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} in {:.2?} wall-clock, {:.2?} CPU on {} {}",
+            self.days,
+            if self.days == 1 { "day" } else { "days" },
+            self.wall_time,
+            self.cpu_time,
+            self.threads,
+            if self.threads == 1 {
+                "thread"
+            } else {
+                "threads"
+            },
+        )
+    }
+}
+
+/// Parses `input` and solves each of the parts in `settings`, timing every
+/// stage
+pub fn run_day(day: &'static Day, input: &str, settings: &Settings) -> DayResult {
+    let job = Job {
+        day,
+        input: Ok(Cow::Borrowed(input)),
+    };
+    let mut result = None;
+    run_days(&[job], settings, |day_result| result = Some(day_result));
+    result.unwrap()
+}
+
+/// Solves the parts in `settings` while reading `input`, for days that
+/// [can be streamed](Day::can_stream). The parts are solved together in one
+/// pass, so each of them gets the time and allocations of the whole pass,
+/// and there's no parse time. The settings' timeout, progress and trace
+/// aren't used, and an overflow fails the whole day.
+pub fn stream_day(day: &'static Day, input: &mut dyn BufRead, settings: &Settings) -> DayResult {
+    let parts = &settings.parts;
+    let context = Context::new(day, &settings.params);
+    let start = Instant::now();
+    let (answers, allocations) =
+        allocations::measure(|| check(settings.checked, || day.stream(input, parts, &context)));
+    let time = start.elapsed();
+    let answers = match answers {
+        Ok(Some(Ok(answers))) => answers,
+        Ok(Some(Err(error))) => return DayResult::failed(day.number, error),
+        Ok(None) => return DayResult::failed(day.number, "this day can't be streamed"),
+        Err(overflow) => return DayResult::failed(day.number, overflow),
+    };
+    DayResult {
+        day: day.number,
+        parse_time: None,
+        parts: parts
+            .iter()
+            .zip(answers)
+            .map(|(&part, answer)| PartResult {
+                part,
+                error: answer.as_ref().err().cloned(),
+                answer: answer.ok(),
+                time,
+                timed_out: false,
+                allocations,
+                overflow: None,
+                panic: None,
+            })
+            .collect(),
+        error: None,
+        panicked: false,
+    }
+}
+
+/// Runs `f` through [`overflow::checked`] if `checked`, with the overflow
+/// as an error message
+fn check<T>(checked: bool, f: impl FnOnce() -> T) -> Result<T, String> {
+    match checked {
+        true => overflow::checked(f).map_err(|overflow| overflow.to_string()),
+        false => Ok(f()),
+    }
+}
+
+/// Runs `f`, turning a panic into its message, so that a bug in one day
+/// doesn't take the others down with it. The message is only reported in
+/// the day's result, rather than also printed in the middle of the output.
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    differential::catch_quietly(f)
+}
+
+enum Task {
+    Parse(usize),
+    /// Solves one of the parts for a parsed job
+    Solve(usize, usize),
+}
+
+/// A parsed job waiting for its parts to be solved
+#[derive(Default)]
+struct Pending {
+    parsed: Option<Arc<Parsed>>,
+    parse_time: Duration,
+    parts: Vec<Option<PartResult>>,
+}
+
+/// Runs every job like [`run_day`] on the settings' worker threads, parsing
+/// different days and solving the parts of a day at the same time.
+/// `report` is called with each day's result in the order of `jobs`, as
+/// soon as that day and every one before it have finished.
+pub fn run_days(jobs: &[Job], settings: &Settings, mut report: impl FnMut(DayResult)) -> Summary {
+    let Settings {
+        parts,
+        params,
+        threads,
+        timeout,
+        progress: listener,
+        trace: sink,
+        checked,
+    } = settings;
+    let start = Instant::now();
+    let pending: Vec<Mutex<Pending>> = jobs.iter().map(|_| Mutex::default()).collect();
+    let (finished, results) = mpsc::channel();
+
+    let work = |task, queue: &pool::Queue<Task>| match task {
+        Task::Parse(index) => {
+            let day = jobs[index].day;
+            let start = Instant::now();
+            let parsed = match &jobs[index].input {
+                Ok(input) => match catch_panic(|| check(*checked, || day.parse(input))) {
+                    Ok(parsed) => {
+                        parsed.and_then(|parsed| parsed.map_err(|error| error.to_string()))
+                    }
+                    Err(message) => {
+                        let result = DayResult {
+                            panicked: true,
+                            ..DayResult::failed(day.number, format!("parsing panicked: {message}"))
+                        };
+                        finished.send((index, result)).unwrap();
+                        return;
+                    }
+                },
+                Err(error) => Err(error.clone()),
+            };
+            let parse_time = start.elapsed();
+            match parsed {
+                Err(error) => finished
+                    .send((index, DayResult::failed(day.number, error)))
+                    .unwrap(),
+                Ok(_) if parts.is_empty() => {
+                    let result = DayResult {
+                        day: day.number,
+                        parse_time: Some(parse_time),
+                        parts: Vec::new(),
+                        error: None,
+                        panicked: false,
+                    };
+                    finished.send((index, result)).unwrap();
+                }
+                Ok(parsed) => {
+                    *pending[index].lock().unwrap() = Pending {
+                        parsed: Some(Arc::new(parsed)),
+                        parse_time,
+                        parts: vec![None; parts.len()],
+                    };
+                    for part in 0..parts.len() {
+                        queue.push(Task::Solve(index, part));
+                    }
+                }
+            }
+        }
+        Task::Solve(index, part) => {
+            let day = jobs[index].day;
+            let parsed = pending[index].lock().unwrap().parsed.clone().unwrap();
+            let cancellation = timeout.map_or_else(Cancellation::never, Cancellation::with_timeout);
+            let context = Context::new(day, params).with_cancellation(&cancellation);
+            let start = Instant::now();
+            let solve = || {
+                allocations::measure(|| {
+                    catch_panic(|| {
+                        let solve = || cancel::catch(|| day.solve(parts[part], &parsed, &context));
+                        match checked {
+                            true => overflow::checked(solve),
+                            false => Ok(solve()),
+                        }
+                    })
+                })
+            };
+            let traced = || match sink {
+                Some(sink) => trace::record(sink, day.number, parts[part], solve),
+                None => solve(),
+            };
+            let (answer, allocations) = match listener {
+                Some(listener) => progress::watch(listener, day.number, parts[part], traced),
+                None => traced(),
+            };
+            let mut part_result = PartResult {
+                part: parts[part],
+                answer: None,
+                time: start.elapsed(),
+                timed_out: false,
+                allocations,
+                error: None,
+                overflow: None,
+                panic: None,
+            };
+            match answer {
+                Ok(Ok(Ok(Some(Ok(answer))))) => part_result.answer = Some(answer),
+                Ok(Ok(Ok(Some(Err(error))))) => part_result.error = Some(error),
+                Ok(Ok(Ok(None))) => {}
+                Ok(Ok(Err(Cancelled))) => part_result.timed_out = true,
+                Ok(Err(overflow)) => part_result.overflow = Some(overflow),
+                Err(message) => part_result.panic = Some(message),
+            }
+
+            let mut pending = pending[index].lock().unwrap();
+            pending.parts[part] = Some(part_result);
+            if pending.parts.iter().all(Option::is_some) {
+                let Pending {
+                    parse_time, parts, ..
+                } = mem::take(&mut *pending);
+                let result = DayResult {
+                    day: day.number,
+                    parse_time: Some(parse_time),
+                    panicked: parts.iter().flatten().any(|part| part.panic.is_some()),
+                    parts: parts.into_iter().map(Option::unwrap).collect(),
+                    error: None,
+                };
+                finished.send((index, result)).unwrap();
+            }
+        }
+    };
+
+    let mut cpu_time = Duration::ZERO;
+    thread::scope(|scope| {
+        scope.spawn(|| pool::run(*threads, (0..jobs.len()).map(Task::Parse), work));
+
+        // Hold back results that finish early until the days before them
+        // have been reported
+        let mut waiting = BTreeMap::new();
+        let mut next = 0;
+        for (index, result) in results.iter().take(jobs.len()) {
+            waiting.insert(index, result);
+            while let Some(result) = waiting.remove(&next) {
+                cpu_time += result.parse_time.unwrap_or_default()
+                    + result.parts.iter().map(|part| part.time).sum::<Duration>();
+                report(result);
+                next += 1;
+            }
+        }
+    });
+
+    Summary {
+        days: jobs.len(),
+        threads: *threads,
+        wall_time: start.elapsed(),
+        cpu_time,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{examples, get_day};
+
+    #[test]
+    fn running_a_day() {
+        let day = get_day(14).unwrap();
+        let result = run_day(day, "498,4 -> 498,6 -> 496,6", &Settings::default());
+        assert!(result.error.is_none());
+        assert_eq!(result.parts[0].answer, Some(Answer::Number(0)));
+        assert_eq!(result.parts[1].answer, None);
+
+        let json = result.to_json();
+        assert_eq!(json.get("day"), Some(&Json::Integer(14)));
+        let parts = json.get("parts").and_then(Json::as_array).unwrap();
+        assert_eq!(parts[0].get("answer"), Some(&Json::Integer(0)));
+        assert_eq!(parts[1].get("answer"), Some(&Json::Null));
+        assert_eq!(json.get("error"), Some(&Json::Null));
+    }
+
+    #[test]
+    fn errors_in_json() {
+        let day = get_day(2).unwrap();
+        let result = run_day(day, "A Y\nB Q", &Settings::default());
+        assert!(result.parts.is_empty());
+        let json = result.to_json();
+        assert_eq!(json.get("parse_ns"), Some(&Json::Null));
+        assert_eq!(
+            json.get("error").and_then(Json::as_str),
+            Some("day 2, line 2, column 3: expected `X`, `Y` or `Z`, found `Q`")
+        );
+    }
+
+    #[test]
+    fn running_days_in_order() {
+        let jobs: Vec<_> = [6, 2, 4, 1]
+            .into_iter()
+            .map(|number| {
+                let day = get_day(number).unwrap();
+                Job {
+                    day,
+                    input: Ok(Cow::Borrowed(examples::input(&format!("day{number}")))),
+                }
+            })
+            .chain([Job {
+                day: get_day(3).unwrap(),
+                input: Err("no input".to_string()),
+            }])
+            .collect();
+
+        let mut results = Vec::new();
+        let settings = Settings {
+            threads: 4,
+            ..Settings::default()
+        };
+        let summary = run_days(&jobs, &settings, |result| results.push(result));
+        let days: Vec<_> = results.iter().map(|result| result.day).collect();
+        assert_eq!(days, [6, 2, 4, 1, 3]);
+        let answers: Vec<_> = results[..4]
+            .iter()
+            .map(|result| result.parts[1].answer.clone().unwrap().to_string())
+            .collect();
+        assert_eq!(answers, ["19", "12", "4", "45000"]);
+        assert_eq!(results[4].error.as_deref(), Some("no input"));
+        assert_eq!((summary.days, summary.threads), (5, 4));
+    }
+
+    #[test]
+    fn timing_out() {
+        // Part 1 of day 15 never checks for cancellation, so it still
+        // finishes, but part 2 stops at its first row
+        let mut settings = Settings {
+            timeout: Some(Duration::ZERO),
+            ..Settings::default()
+        };
+        settings.params.set(15, "row", 10).unwrap();
+        let result = run_day(get_day(15).unwrap(), examples::input("day15"), &settings);
+        assert!(!result.parts[0].timed_out);
+        assert_eq!(result.parts[0].answer, Some(Answer::Number(26)));
+        assert!(result.parts[1].timed_out);
+        assert_eq!(result.parts[1].answer, None);
+        let json = result.to_json();
+        let parts = json.get("parts").and_then(Json::as_array).unwrap();
+        assert_eq!(parts[1].get("timed_out"), Some(&Json::Bool(true)));
+    }
+
+    #[test]
+    fn streaming_a_day() {
+        let day = get_day(2).unwrap();
+        let settings = Settings {
+            parts: vec![Part::Two],
+            ..Settings::default()
+        };
+        let result = stream_day(day, &mut examples::input("day2").as_bytes(), &settings);
+        assert_eq!(result.parse_time, None);
+        assert_eq!(result.parts.len(), 1);
+        assert_eq!(result.parts[0].answer, Some(Answer::Number(12)));
+
+        let result = stream_day(get_day(5).unwrap(), &mut "".as_bytes(), &settings);
+        assert_eq!(result.error.as_deref(), Some("this day can't be streamed"));
+    }
+
+    #[test]
+    fn panicking_part() {
+        let result = run_day(&crate::tests::PANICKING, "", &Settings::default());
+        assert!(result.panicked);
+        assert_eq!(result.error, None);
+        assert_eq!(result.parts[0].panic, None);
+        assert_eq!(result.parts[0].answer, Some(Answer::Number(1)));
+        assert_eq!(result.parts[1].panic.as_deref(), Some("a bug in part 2"));
+        assert_eq!(result.parts[1].answer, None);
+
+        let json = result.to_json();
+        assert_eq!(json.get("panicked"), Some(&Json::Bool(true)));
+        let parts = json.get("parts").and_then(Json::as_array).unwrap();
+        assert!(parts[1].get("panic").and_then(Json::as_str).is_some());
+    }
+
+    #[test]
+    fn part_without_answer() {
+        let mut settings = Settings::default();
+        settings.params.set(7, "update_size", 80000000).unwrap();
+        let result = run_day(get_day(7).unwrap(), examples::input("day7"), &settings);
+        assert!(!result.panicked);
+        assert_eq!(result.parts[0].error, None);
+        assert_eq!(result.parts[1].answer, None);
+        assert_eq!(
+            result.parts[1].error.as_deref(),
+            Some("no directory is bigger than the 58381165 that has to be deleted")
+        );
+        let json = result.to_json();
+        let parts = json.get("parts").and_then(Json::as_array).unwrap();
+        assert!(parts[1].get("error").and_then(Json::as_str).is_some());
+    }
+
+    #[test]
+    fn checking_for_overflows() {
+        let day = get_day(1).unwrap();
+        let settings = Settings {
+            checked: true,
+            ..Settings::default()
+        };
+        let result = run_day(day, "4000000000\n\n4000000000\n\n1", &settings);
+        assert_eq!(result.parts[0].answer, Some(Answer::Number(4000000000)));
+        let parsed = run_day(day, "4000000000\n4000000000\n\n1\n\n1", &settings);
+        if cfg!(feature = "wide-ints") {
+            assert_eq!(result.parts[1].answer, Some(Answer::Number(8000000001)));
+            assert_eq!(parsed.error, None);
+            return;
+        }
+
+        let overflow = result.parts[1].overflow.unwrap();
+        assert_eq!(overflow.location.file(), "src/day1.rs");
+        assert_eq!(overflow.expression(), "4000000000 + 4000000000");
+        assert_eq!(result.parts[1].answer, None);
+        let json = result.to_json();
+        let parts = json.get("parts").and_then(Json::as_array).unwrap();
+        assert_eq!(
+            parts[1]
+                .get("overflow")
+                .and_then(|overflow| overflow.get("type")),
+            Some(&Json::from("u32"))
+        );
+
+        // Overflowing while parsing fails the whole day
+        assert!(parsed.parts.is_empty());
+        assert!(parsed
+            .error
+            .unwrap()
+            .ends_with("4000000000 + 4000000000 doesn't fit in u32"));
+    }
+}
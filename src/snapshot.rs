@@ -0,0 +1,204 @@
+//! Snapshot tests for the text the solutions draw, like day 10's CRT or
+//! day 14's cave.
+//!
+//! Each rendering is kept in `snapshots/NAME.txt`, and [`assert_snapshot`]
+//! fails with a diff when it changes. The new rendering is saved next to
+//! the old one as `NAME.new.txt`, so once it looks right it can be accepted
+//! with `aoc snapshots --accept`, or by running the tests again with
+//! `UPDATE_SNAPSHOTS=1`, which is also how new snapshots are first written.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Where the snapshots are kept
+pub const DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/snapshots");
+
+/// Overwrites the snapshots with what the tests render when set to `1`
+pub const UPDATE_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// The lines around each change that a diff shows
+const CONTEXT: usize = 2;
+
+fn path(name: &str) -> PathBuf {
+    PathBuf::from(DIR).join(format!("{name}.txt"))
+}
+
+fn new_path(name: &str) -> PathBuf {
+    PathBuf::from(DIR).join(format!("{name}.new.txt"))
+}
+
+/// Reads a snapshot, or returns `None` if it hasn't been written yet
+fn read(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text.replace("\r\n", "\n"))),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Checks `rendered` against the snapshot called `name`, panicking with a
+/// diff if it's different or there's no snapshot yet
+pub fn assert_snapshot(name: &str, rendered: &str) {
+    let rendered = format!("{rendered}\n");
+    let update = std::env::var(UPDATE_VAR).is_ok_and(|value| value == "1");
+    let saved = read(&path(name)).unwrap_or_else(|error| panic!("snapshot {name}: {error}"));
+    let new_path = new_path(name);
+    if saved.as_deref() == Some(&rendered) {
+        // Any rendering waiting to be accepted is out of date
+        let _ = fs::remove_file(new_path);
+        return;
+    }
+    if update {
+        fs::create_dir_all(DIR)
+            .and_then(|()| fs::write(path(name), &rendered))
+            .unwrap_or_else(|error| panic!("snapshot {name}: {error}"));
+        let _ = fs::remove_file(new_path);
+        return;
+    }
+
+    fs::create_dir_all(DIR)
+        .and_then(|()| fs::write(&new_path, &rendered))
+        .unwrap_or_else(|error| panic!("snapshot {name}: {error}"));
+    let hint = format!("accept it with `aoc snapshots --accept` or {UPDATE_VAR}=1");
+    match saved {
+        Some(saved) => panic!(
+            "snapshot {name} has changed, {hint}:\n{}",
+            diff(&saved, &rendered)
+        ),
+        None => panic!("snapshot {name} hasn't been written yet, {hint}:\n{rendered}"),
+    }
+}
+
+/// A rendering that's different from its snapshot, waiting to be accepted
+pub struct Pending {
+    pub name: String,
+    /// `None` for a new snapshot
+    pub old: Option<String>,
+    pub new: String,
+}
+
+impl Pending {
+    /// Replaces the snapshot with the new rendering
+    pub fn accept(&self) -> io::Result<()> {
+        fs::rename(new_path(&self.name), path(&self.name))
+    }
+}
+
+/// Every rendering waiting to be accepted, by name
+pub fn pending() -> io::Result<Vec<Pending>> {
+    let entries = match fs::read_dir(DIR) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    let mut pending = Vec::new();
+    for entry in entries {
+        let file_name = entry?.file_name();
+        let Some(name) = file_name
+            .to_str()
+            .and_then(|file_name| file_name.strip_suffix(".new.txt"))
+        else {
+            continue;
+        };
+        pending.push(Pending {
+            name: name.to_string(),
+            old: read(&path(name))?,
+            new: fs::read_to_string(new_path(name))?,
+        });
+    }
+    pending.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(pending)
+}
+
+/// The lines that differ between `old` and `new`, marked with `-` and `+`,
+/// with a few unchanged lines around them for context
+pub fn diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // The length of the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            lines.push(('-', old[i]));
+            i += 1;
+        } else {
+            lines.push(('+', new[j]));
+            j += 1;
+        }
+    }
+
+    let changed: Vec<usize> = (0..lines.len())
+        .filter(|&index| lines[index].0 != ' ')
+        .collect();
+    let near_change = |index: usize| {
+        changed
+            .iter()
+            .any(|&change| change.abs_diff(index) <= CONTEXT)
+    };
+    let mut diff = String::new();
+    let mut skipped = false;
+    for (index, (marker, line)) in lines.into_iter().enumerate() {
+        if near_change(index) {
+            diff.push_str(&format!("{marker} {line}\n"));
+            skipped = false;
+        } else if !skipped {
+            diff.push_str("  ...\n");
+            skipped = true;
+        }
+    }
+    diff
+}
+
+/// The example inputs for `day`, named after them, followed by the real
+/// input, named `dayN_input`
+#[cfg(test)]
+pub(crate) fn inputs(day: u8) -> Vec<(String, &'static str)> {
+    let mut inputs: Vec<_> = crate::examples::for_day(day)
+        .map(|example| (example.name.to_string(), example.input))
+        .collect();
+    let input = crate::get_day(day).unwrap().input;
+    inputs.push((format!("day{day}_input"), input));
+    inputs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffs() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n9";
+        assert_eq!(
+            diff(old, new),
+            "  ...
+  3
+  4
+- 5
++ five
+  6
+  7
+  ...
+"
+        );
+        assert_eq!(diff("a\nb", "a\nb"), "  ...\n");
+    }
+}
@@ -0,0 +1,114 @@
+use std::fmt;
+
+use crate::cancel::{self, Cancellation};
+use crate::params::{Param, Params};
+use crate::parse::ParseError;
+use crate::Day;
+
+/// A day's puzzle, split into the stages every solution goes through.
+///
+/// The input is parsed once and both parts are solved from the parsed
+/// model, so parsing can be timed separately from solving. The parts may
+/// be solved at the same time on different threads.
+pub trait Solution {
+    type Parsed: Send + Sync + 'static;
+
+    /// Values from the puzzle text the parts read from their [`Context`]
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+
+    /// The error says why the input has no answer, like there being no
+    /// marker as long as a parameter asks for
+    fn part1(parsed: &Self::Parsed, context: &Context) -> Result<Answer, String>;
+
+    /// `None` if part 2 hasn't been solved yet
+    fn part2(parsed: &Self::Parsed, context: &Context) -> Option<Result<Answer, String>>;
+}
+
+/// Everything a part can use besides its parsed input
+pub struct Context<'a> {
+    day: &'a Day,
+    params: &'a Params,
+    cancellation: &'a Cancellation,
+}
+
+static DEFAULT_PARAMS: Params = Params::new();
+
+impl<'a> Context<'a> {
+    pub fn new(day: &'a Day, params: &'a Params) -> Self {
+        Context {
+            day,
+            params,
+            cancellation: &cancel::NEVER,
+        }
+    }
+
+    /// Solves with every parameter at its default
+    pub fn with_defaults(day: &'a Day) -> Self {
+        Context::new(day, &DEFAULT_PARAMS)
+    }
+
+    /// Lets the part be stopped early through `cancellation`
+    pub fn with_cancellation(self, cancellation: &'a Cancellation) -> Self {
+        Context {
+            cancellation,
+            ..self
+        }
+    }
+
+    /// What long loops should [`check`](Cancellation::check) on each
+    /// iteration
+    pub fn cancellation(&self) -> &'a Cancellation {
+        self.cancellation
+    }
+
+    /// One of the day's [`Solution::PARAMS`]. Panics if the day doesn't
+    /// have it, or if its range allows values that don't fit in `T`.
+    pub fn param<T: TryFrom<i64>>(&self, name: &str) -> T {
+        let value = self.params.get(self.day, name);
+        T::try_from(value).unwrap_or_else(|_| {
+            panic!(
+                "day {} parameter `{name}` = {value} doesn't fit in {}",
+                self.day.number,
+                std::any::type_name::<T>()
+            )
+        })
+    }
+}
+
+/// The answer to one part of a puzzle
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{number}"),
+            Answer::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(number: $t) -> Self {
+                    Answer::Number(i64::try_from(number).expect("answer doesn't fit in an i64"))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(i32, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
@@ -0,0 +1,169 @@
+//! Solving days in one pass over their input as it's read, so inputs far
+//! bigger than memory can be piped in.
+//!
+//! Days that only walk their input once, like summing up day 2's rounds,
+//! implement [`Streaming`] as well as [`Solution`]. Instead of parsing the
+//! whole input first, they keep as little of it as the parts need, like a
+//! window of the last few characters in day 6, and solve every part as they
+//! go. Lines are parsed the same way as with [`Solution::parse`], so they
+//! accept the same inputs and report errors at the same places.
+
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::parse::{self, Line, ParseError};
+use crate::{Answer, Context, Part, Solution};
+
+/// A [`Solution`] that can also solve its parts while reading its input
+pub trait Streaming: Solution {
+    /// Solves each of `parts` in one pass over `input`, returning their
+    /// answers in the same order, or why the input has none like
+    /// [`Solution::part1`]. What's kept of the input must not grow with its
+    /// length, unless an answer can't be found without it, which the
+    /// implementation documents.
+    fn stream(
+        input: &mut dyn BufRead,
+        parts: &[Part],
+        context: &Context,
+    ) -> Result<Vec<Result<Answer, String>>, StreamError>;
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Read(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamError::Read(error) if error.kind() == io::ErrorKind::InvalidData => {
+                write!(f, "input is not valid UTF-8")
+            }
+            StreamError::Read(error) => write!(f, "could not read input: {error}"),
+            StreamError::Parse(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(error: io::Error) -> Self {
+        StreamError::Read(error)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(error: ParseError) -> Self {
+        StreamError::Parse(error)
+    }
+}
+
+/// Calls `f` on each line of `input`, numbered and split like
+/// [`parse::lines`] and with trailing whitespace dropped like
+/// [`parse::normalize`], reading one line at a time
+pub fn for_each_line(
+    input: &mut dyn BufRead,
+    mut f: impl FnMut(Line) -> Result<(), ParseError>,
+) -> Result<(), StreamError> {
+    let mut text = String::new();
+    let mut number = 0;
+    // Blank lines are held back until there's a line after them, since
+    // they're dropped at the end of the input
+    let mut blank = 0;
+    loop {
+        text.clear();
+        if input.read_line(&mut text)? == 0 {
+            return Ok(());
+        }
+        number += 1;
+        let line = text.trim_end();
+        if line.is_empty() {
+            blank += 1;
+            continue;
+        }
+        for number in number - blank..number {
+            f(Line::new(number, ""))?;
+        }
+        blank = 0;
+        f(Line::new(number, line))?;
+    }
+}
+
+/// Skips the [`BOM`](parse::BOM) at the start of `input`, if it has one
+pub(crate) fn skip_bom(input: &mut dyn BufRead) -> io::Result<()> {
+    if input.fill_buf()?.starts_with(parse::BOM.as_bytes()) {
+        input.consume(parse::BOM.len());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{examples, get_day, DAYS};
+
+    #[test]
+    fn lines_like_parse() {
+        let mut lines = Vec::new();
+        for_each_line(&mut "a\r\nb  \n\nc\t\r\n\n \n".as_bytes(), |line| {
+            lines.push((line.line_number(), line.text().to_string()));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(
+            lines,
+            [(1, "a"), (2, "b"), (3, ""), (4, "c")].map(|(n, s)| (n, s.to_string()))
+        );
+    }
+
+    #[test]
+    fn streaming_matches_parsing() {
+        for example in examples::EXAMPLES {
+            let day = get_day(example.day).unwrap();
+            let params = example.params();
+            let context = Context::new(day, &params);
+            let Some(streamed) = day.stream(&mut example.input.as_bytes(), &Part::ALL, &context)
+            else {
+                continue;
+            };
+            let parsed = day.parse(example.input).unwrap();
+            let solved: Vec<_> = Part::ALL
+                .iter()
+                .map(|&part| day.solve(part, &parsed, &context).unwrap())
+                .collect();
+            assert_eq!(streamed.unwrap(), solved, "{}", example.name);
+        }
+
+        let streaming: Vec<_> = DAYS
+            .iter()
+            .filter(|day| day.can_stream())
+            .map(|day| day.number)
+            .collect();
+        assert_eq!(streaming, [1, 2, 3, 4, 6, 9, 10]);
+    }
+
+    #[test]
+    fn single_parts() {
+        let day = get_day(1).unwrap();
+        let context = Context::with_defaults(day);
+        let input = examples::input("day1");
+        let answers = day.stream(&mut input.as_bytes(), &[Part::Two], &context);
+        assert_eq!(answers.unwrap().unwrap(), [Ok(Answer::Number(45000))]);
+    }
+
+    #[test]
+    fn errors_like_parse() {
+        let day = get_day(2).unwrap();
+        let context = Context::with_defaults(day);
+        let error = day
+            .stream(&mut "A Y\nB Q".as_bytes(), &Part::ALL, &context)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            day.parse("A Y\nB Q").err().unwrap().to_string()
+        );
+    }
+}
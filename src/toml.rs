@@ -0,0 +1,213 @@
+//! A small subset of TOML, enough for the answers and parameter files:
+//! `[table]` headers followed by `key = value` lines, where a value is an
+//! integer, a `"string"` or a `"""multi-line string"""`, and `#` starts a
+//! comment.
+
+use crate::parse::{self, Line, Lines, ParseError};
+
+pub struct Table<'a> {
+    pub name: &'a str,
+    /// Positioned at the name, for reporting a table that isn't expected
+    pub line: Line<'a>,
+    pub entries: Vec<Entry<'a>>,
+}
+
+pub struct Entry<'a> {
+    pub key: &'a str,
+    /// Positioned at the key, for reporting a key that isn't expected
+    pub line: Line<'a>,
+    pub value: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    Integer(i64),
+    String(String),
+}
+
+impl Value {
+    /// Integers as they're written, strings as they are
+    pub fn into_string(self) -> String {
+        match self {
+            Value::Integer(number) => number.to_string(),
+            Value::String(string) => string,
+        }
+    }
+}
+
+/// Parses every table. `expected_table` describes the table headers the
+/// caller wants, for the error if a key comes before the first one.
+pub fn parse<'a>(text: &'a str, expected_table: &str) -> Result<Vec<Table<'a>>, ParseError> {
+    let mut tables: Vec<Table> = Vec::new();
+    let mut lines = parse::lines(text);
+
+    while let Some(mut line) = lines.next() {
+        line.take_while(char::is_whitespace);
+        if line.rest().is_empty() || line.rest().starts_with('#') {
+            continue;
+        }
+
+        if line.eat("[") {
+            let name_line = line;
+            let name = line.take_while(is_key_char);
+            if name.is_empty() {
+                return Err(line.error("a table name"));
+            }
+            line.expect("]")?;
+            finish_line(line)?;
+            tables.push(Table {
+                name,
+                line: name_line,
+                entries: Vec::new(),
+            });
+            continue;
+        }
+
+        let Some(table) = tables.last_mut() else {
+            return Err(line.error(expected_table));
+        };
+        let key_line = line;
+        let key = line.take_while(is_key_char);
+        if key.is_empty() {
+            return Err(line.error("a key"));
+        }
+        line.take_while(char::is_whitespace);
+        line.expect("=")?;
+        line.take_while(char::is_whitespace);
+        let value = parse_value(line, &mut lines)?;
+        table.entries.push(Entry {
+            key,
+            line: key_line,
+            value,
+        });
+    }
+
+    Ok(tables)
+}
+
+/// Bare keys and table names, like `day15` or `max_coord`
+fn is_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Parses an integer, a `"string"` or a `"""multi-line string"""`, which may
+/// continue onto the following lines
+fn parse_value<'a>(mut line: Line<'a>, lines: &mut Lines<'a>) -> Result<Value, ParseError> {
+    if line.eat("\"\"\"") {
+        // Like TOML, a newline straight after the opening quotes is ignored
+        let mut value = String::new();
+        let mut first = true;
+        loop {
+            if let Some(before) = line.take_until("\"\"\"") {
+                value += before;
+                finish_line(line)?;
+                return Ok(Value::String(value));
+            }
+            if !(first && line.rest().is_empty()) {
+                value += line.rest();
+                value.push('\n');
+            }
+            first = false;
+            line = lines.expect_line("the closing `\"\"\"`")?;
+        }
+    } else if line.eat("\"") {
+        let mut value = String::new();
+        loop {
+            match line.next_char() {
+                Some('"') => break,
+                Some('\\') => match line.next_char() {
+                    Some('n') => value.push('\n'),
+                    Some('"') => value.push('"'),
+                    Some('\\') => value.push('\\'),
+                    _ => return Err(line.error("an escape sequence `\\n`, `\\\"` or `\\\\`")),
+                },
+                Some(c) => value.push(c),
+                None => return Err(line.error("the closing `\"`")),
+            }
+        }
+        finish_line(line)?;
+        Ok(Value::String(value))
+    } else {
+        let number = line.number()?;
+        finish_line(line)?;
+        Ok(Value::Integer(number))
+    }
+}
+
+/// Allows trailing whitespace and comments
+fn finish_line(mut line: Line) -> Result<(), ParseError> {
+    line.take_while(char::is_whitespace);
+    if line.rest().starts_with('#') {
+        return Ok(());
+    }
+    line.finish()
+}
+
+/// The number of a `[dayN]` table
+pub fn day_number(table: &Table) -> Result<u8, ParseError> {
+    table
+        .name
+        .strip_prefix("day")
+        .filter(|number| number.bytes().all(|c| c.is_ascii_digit()))
+        .and_then(|number| number.parse().ok())
+        .ok_or_else(|| {
+            let column = table.line.column();
+            table
+                .line
+                .error_at(column, table.name, "a table like `[day1]`")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_and_values() {
+        let tables = parse(
+            r#"
+# A comment
+[day1]
+part1 = 24000
+part2 = "45000" # trailing comment
+
+[day10]
+part2 = """
+##..
+.##."""
+"#,
+            "a table",
+        )
+        .unwrap();
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].name, "day1");
+        assert_eq!(day_number(&tables[1]), Ok(10));
+        let entries: Vec<_> = tables[0]
+            .entries
+            .iter()
+            .map(|entry| (entry.key, entry.value.clone()))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                ("part1", Value::Integer(24000)),
+                ("part2", Value::String("45000".to_string()))
+            ]
+        );
+        assert_eq!(
+            tables[1].entries[0].value,
+            Value::String("##..\n.##.".to_string())
+        );
+    }
+
+    #[test]
+    fn day_tables() {
+        let tables = parse("[day15x]\n[days]\n[day3]", "a table").unwrap();
+        assert_eq!(
+            day_number(&tables[0]).unwrap_err().to_string(),
+            "line 1, column 2: expected a table like `[day1]`, found `day15x`"
+        );
+        assert!(day_number(&tables[1]).is_err());
+        assert_eq!(day_number(&tables[2]), Ok(3));
+    }
+}
@@ -0,0 +1,167 @@
+//! Step-by-step traces of the simulations, for debugging them or watching
+//! how they work.
+//!
+//! Simulations call [`emit`] with a typed [`Event`] at each step, like a
+//! crane move in day 5. That does nothing unless the part is being run
+//! through [`record`], as the runner does when its settings have a
+//! [`Sink`]. Each event becomes a JSON object with the day, part and step
+//! it's from, the event's name and its fields, so `aoc run --trace` can
+//! write one per line for a replay tool to step through:
+//!
+//! ```text
+//! {"day":5,"part":1,"step":0,"event":"move","count":1,"from":2,"to":1,"crates":"D"}
+//! ```
+
+use std::cell::RefCell;
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+use crate::allocations;
+use crate::json::Json;
+use crate::Part;
+
+/// Something that happened during a simulation
+pub trait Event {
+    /// What kind of event this is, like `move`
+    fn name(&self) -> &'static str;
+
+    /// Adds the event's details to `object`
+    fn fields(&self, object: Json) -> Json;
+}
+
+/// Where the events of every part run through [`record`] go. It can be
+/// called from several threads at once.
+pub trait Sink: Send + Sync {
+    fn write(&self, event: Json);
+}
+
+impl fmt::Debug for dyn Sink {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Sink")
+    }
+}
+
+/// Writes each event on its own line
+pub struct JsonLines<W> {
+    /// The writer, and the first error it returned
+    out: Mutex<(W, Option<io::Error>)>,
+}
+
+impl<W: Write> JsonLines<W> {
+    pub fn new(out: W) -> Self {
+        JsonLines {
+            out: Mutex::new((out, None)),
+        }
+    }
+
+    /// Flushes the writer, returning the first error from writing to it
+    pub fn flush(&self) -> io::Result<()> {
+        let (out, error) = &mut *self.out.lock().unwrap();
+        match error.take() {
+            Some(error) => Err(error),
+            None => out.flush(),
+        }
+    }
+}
+
+impl<W: Write + Send> Sink for JsonLines<W> {
+    fn write(&self, event: Json) {
+        let (out, error) = &mut *self.out.lock().unwrap();
+        if error.is_none() {
+            if let Err(err) = writeln!(out, "{event}") {
+                *error = Some(err);
+            }
+        }
+    }
+}
+
+struct Recording {
+    sink: Arc<dyn Sink>,
+    day: u8,
+    part: Part,
+    step: u64,
+}
+
+thread_local! {
+    static RECORDING: RefCell<Option<Recording>> = const { RefCell::new(None) };
+}
+
+/// Adds the event made by `event` to the trace, if the part is being
+/// recorded. Events are only made when they'll be written, so they can
+/// copy whatever they need.
+pub fn emit<E: Event>(event: impl FnOnce() -> E) {
+    RECORDING.with_borrow_mut(|recording| {
+        if let Some(recording) = recording {
+            allocations::ignoring(|| {
+                let event = event();
+                let object = Json::object()
+                    .with("day", recording.day)
+                    .with("part", recording.part.number())
+                    .with("step", recording.step)
+                    .with("event", event.name());
+                recording.sink.write(event.fields(object));
+                recording.step += 1;
+            });
+        }
+    });
+}
+
+/// Runs `f`, which solves `part` of `day`, writing the events it emits to
+/// `sink`
+pub fn record<T>(sink: &Arc<dyn Sink>, day: u8, part: Part, f: impl FnOnce() -> T) -> T {
+    /// Stops recording even if `f` unwinds
+    struct Stop;
+
+    impl Drop for Stop {
+        fn drop(&mut self) {
+            RECORDING.set(None);
+        }
+    }
+
+    RECORDING.set(Some(Recording {
+        sink: Arc::clone(sink),
+        day,
+        part,
+        step: 0,
+    }));
+    let _stop = Stop;
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Note(&'static str);
+
+    impl Event for Note {
+        fn name(&self) -> &'static str {
+            "note"
+        }
+
+        fn fields(&self, object: Json) -> Json {
+            object.with("text", self.0)
+        }
+    }
+
+    #[test]
+    fn recording_a_part() {
+        let lines = Arc::new(JsonLines::new(Vec::new()));
+        let sink: Arc<dyn Sink> = lines.clone();
+        record(&sink, 3, Part::Two, || {
+            emit(|| Note("first"));
+            emit(|| Note("second"));
+        });
+        emit(|| -> Note { panic!("not recording, so no events are made") });
+
+        lines.flush().unwrap();
+        let written = String::from_utf8(lines.out.lock().unwrap().0.clone()).unwrap();
+        assert_eq!(
+            written,
+            r#"{"day":3,"part":2,"step":0,"event":"note","text":"first"}
+{"day":3,"part":2,"step":1,"event":"note","text":"second"}
+"#
+        );
+    }
+}